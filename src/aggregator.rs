@@ -0,0 +1,116 @@
+use crate::HealthStatus;
+
+/// Reduces the statuses of several indicators into a single status.
+pub trait StatusAggregator {
+    fn aggregate_status(&self, statuses: &[&HealthStatus]) -> HealthStatus;
+}
+
+/// Picks the first status found in a configured order, like Spring's `SimpleStatusAggregator`.
+///
+/// The default order is `DOWN`, `OUT_OF_SERVICE`, `UP`, `UNKNOWN`. Custom statuses can be slotted
+/// anywhere into the order, statuses missing from it rank below all configured ones.
+///
+/// ```
+/// use axum_health::{HealthStatus, SimpleStatusAggregator};
+///
+/// let aggregator = SimpleStatusAggregator::new(vec![
+///     HealthStatus::Down,
+///     HealthStatus::OutOfService,
+///     HealthStatus::Custom("DEGRADED".to_owned()),
+///     HealthStatus::Up,
+///     HealthStatus::Unknown,
+/// ]);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SimpleStatusAggregator {
+    order: Vec<HealthStatus>,
+}
+
+impl SimpleStatusAggregator {
+    pub fn new(order: Vec<HealthStatus>) -> Self {
+        SimpleStatusAggregator { order }
+    }
+
+    fn rank(&self, status: &HealthStatus) -> usize {
+        self.order
+            .iter()
+            .position(|ordered| ordered == status)
+            .unwrap_or(self.order.len())
+    }
+}
+
+impl Default for SimpleStatusAggregator {
+    fn default() -> Self {
+        SimpleStatusAggregator::new(vec![
+            HealthStatus::Down,
+            HealthStatus::OutOfService,
+            HealthStatus::Up,
+            HealthStatus::Unknown,
+        ])
+    }
+}
+
+impl StatusAggregator for SimpleStatusAggregator {
+    fn aggregate_status(&self, statuses: &[&HealthStatus]) -> HealthStatus {
+        // If we have no statuses, we are up, otherwise we take the first one in our order.
+        statuses
+            .iter()
+            .min_by(|a, b| {
+                self.rank(a)
+                    .cmp(&self.rank(b))
+                    .then_with(|| a.code().cmp(b.code()))
+            })
+            .map(|status| (*status).clone())
+            .unwrap_or(HealthStatus::Up)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::aggregator::{SimpleStatusAggregator, StatusAggregator};
+    use crate::HealthStatus;
+
+    #[test]
+    fn test_default_order() {
+        let aggregator = SimpleStatusAggregator::default();
+        let degraded = HealthStatus::Custom("DEGRADED".to_owned());
+
+        assert_eq!(aggregator.aggregate_status(&[]), HealthStatus::Up);
+        assert_eq!(
+            aggregator.aggregate_status(&[&HealthStatus::Unknown, &HealthStatus::Up]),
+            HealthStatus::Up
+        );
+        assert_eq!(
+            aggregator.aggregate_status(&[
+                &HealthStatus::Unknown,
+                &HealthStatus::OutOfService,
+                &HealthStatus::Down
+            ]),
+            HealthStatus::Down
+        );
+        assert_eq!(
+            aggregator.aggregate_status(&[&degraded, &HealthStatus::Unknown]),
+            HealthStatus::Unknown
+        );
+        assert_eq!(aggregator.aggregate_status(&[&degraded]), degraded);
+    }
+
+    #[test]
+    fn test_custom_order() {
+        let degraded = HealthStatus::Custom("DEGRADED".to_owned());
+        let aggregator = SimpleStatusAggregator::new(vec![
+            HealthStatus::Down,
+            degraded.clone(),
+            HealthStatus::Up,
+        ]);
+
+        assert_eq!(
+            aggregator.aggregate_status(&[&HealthStatus::Up, &degraded]),
+            degraded
+        );
+        assert_eq!(
+            aggregator.aggregate_status(&[&degraded, &HealthStatus::Down]),
+            HealthStatus::Down
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::HealthDetail;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+struct Snapshot {
+    detail: HealthDetail,
+    checked: Instant,
+    checked_at: SystemTime,
+}
+
+/// Holds the last [HealthDetail] of an indicator until its time to live has passed.
+pub(crate) struct IndicatorCache {
+    ttl: Duration,
+    snapshot: RwLock<Option<Arc<Snapshot>>>,
+    // Held while evaluating, so a single evaluation runs at a time. Requests arriving meanwhile
+    // are served the previous snapshot instead of waiting for it.
+    refreshing: Mutex<()>,
+}
+
+impl IndicatorCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        IndicatorCache {
+            ttl,
+            snapshot: RwLock::new(None),
+            refreshing: Mutex::new(()),
+        }
+    }
+
+    /// Returns the cached detail, evaluating the indicator if it expired or was never evaluated.
+    /// While another evaluation is running, the expired detail is returned as is.
+    pub(crate) async fn get<F>(&self, evaluate: F) -> HealthDetail
+    where
+        F: Future<Output = HealthDetail>,
+    {
+        let snapshot = self.snapshot();
+        if let Some(cached) = &snapshot {
+            if cached.checked.elapsed() < self.ttl {
+                return with_checked_at(cached);
+            }
+        }
+
+        let _refreshing = match (self.refreshing.try_lock(), &snapshot) {
+            (Ok(refreshing), _) => refreshing,
+            (Err(_), Some(stale)) => return with_checked_at(stale),
+            // Nothing to serve yet, wait for the first evaluation to finish.
+            (Err(_), None) => self.refreshing.lock().await,
+        };
+
+        // Another request may have evaluated the indicator while this one was waiting.
+        match self.snapshot() {
+            Some(cached) if cached.checked.elapsed() < self.ttl => with_checked_at(&cached),
+            _ => with_checked_at(&self.store(evaluate.await)),
+        }
+    }
+
+    /// Evaluates the indicator regardless of the age of the cached detail, unless an evaluation
+    /// is already running.
+    pub(crate) async fn refresh<F>(&self, evaluate: F)
+    where
+        F: Future<Output = HealthDetail>,
+    {
+        if let Ok(_refreshing) = self.refreshing.try_lock() {
+            self.store(evaluate.await);
+        }
+    }
+
+    fn snapshot(&self) -> Option<Arc<Snapshot>> {
+        self.snapshot
+            .read()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+
+    fn store(&self, detail: HealthDetail) -> Arc<Snapshot> {
+        let snapshot = Arc::new(Snapshot::new(detail));
+        *self
+            .snapshot
+            .write()
+            .unwrap_or_else(|error| error.into_inner()) = Some(snapshot.clone());
+        snapshot
+    }
+}
+
+impl Snapshot {
+    fn new(detail: HealthDetail) -> Self {
+        Snapshot {
+            detail,
+            checked: Instant::now(),
+            checked_at: SystemTime::now(),
+        }
+    }
+}
+
+fn with_checked_at(snapshot: &Snapshot) -> HealthDetail {
+    let mut detail = snapshot.detail.clone();
+    detail.with_detail(
+        "checked_at".to_owned(),
+        humantime::format_rfc3339_millis(snapshot.checked_at).to_string(),
+    );
+    detail
+}
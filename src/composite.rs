@@ -0,0 +1,137 @@
+use crate::aggregator::{SimpleStatusAggregator, StatusAggregator};
+use crate::service::{aggregate, Indicators};
+use crate::{HealthDetail, HealthIndicator};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Groups several indicators under a single component, for example all the pools of one
+/// logical database.
+///
+/// The children are evaluated concurrently, their statuses are aggregated into the status of the
+/// composite and they are listed under its `components`.
+///
+/// ```json
+/// {
+///   "status": "UP",
+///   "components": {
+///     "primary": { "status": "UP" },
+///     "replica": { "status": "UP" }
+///   }
+/// }
+/// ```
+pub struct CompositeHealthIndicator {
+    name: String,
+    indicators: Indicators,
+    status_aggregator: Arc<dyn StatusAggregator + Send + Sync + 'static>,
+}
+
+impl CompositeHealthIndicator {
+    pub fn new(name: String) -> Self {
+        CompositeHealthIndicator {
+            name,
+            indicators: Default::default(),
+            status_aggregator: Arc::new(SimpleStatusAggregator::default()),
+        }
+    }
+
+    pub fn with_indicator<I>(mut self, indicator: I) -> Self
+    where
+        I: HealthIndicator + Send + Sync + 'static,
+    {
+        self.indicators
+            .insert(indicator.name(), Arc::new(indicator));
+        self
+    }
+
+    /// Sets how the statuses of the children are combined, defaults to [SimpleStatusAggregator].
+    pub fn with_status_aggregator<A>(mut self, aggregator: A) -> Self
+    where
+        A: StatusAggregator + Send + Sync + 'static,
+    {
+        self.status_aggregator = Arc::new(aggregator);
+        self
+    }
+}
+
+#[async_trait]
+impl HealthIndicator for CompositeHealthIndicator {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        let components = futures::future::join_all(
+            self.indicators
+                .iter()
+                .map(|(name, indicator)| async move { (name.clone(), indicator.details().await) }),
+        )
+        .await
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+
+        let mut detail = HealthDetail::new(aggregate(self.status_aggregator.as_ref(), &components));
+        detail.components = components;
+        detail
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::composite::CompositeHealthIndicator;
+    use crate::service::test::MockHealthIndicator;
+    use crate::{Health, HealthDetail, HealthIndicator, HealthStatus};
+
+    #[tokio::test]
+    async fn test_composite() {
+        let composite = CompositeHealthIndicator::new("db".to_owned())
+            .with_indicator(MockHealthIndicator::new(
+                "primary".to_owned(),
+                HealthDetail::up(),
+            ))
+            .with_indicator(MockHealthIndicator::new(
+                "replica".to_owned(),
+                HealthDetail::down(),
+            ));
+
+        let detail = composite.details().await;
+        assert_eq!(detail.status, HealthStatus::Down);
+        assert_eq!(detail.components["primary"], HealthDetail::up());
+        assert_eq!(detail.components["replica"], HealthDetail::down());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_nested_serialization() {
+        let health = Health::builder()
+            .with_indicator(
+                CompositeHealthIndicator::new("db".to_owned())
+                    .with_indicator(MockHealthIndicator::new(
+                        "primary".to_owned(),
+                        HealthDetail::up(),
+                    ))
+                    .with_indicator(MockHealthIndicator::new(
+                        "replica".to_owned(),
+                        HealthDetail::up(),
+                    )),
+            )
+            .build();
+
+        let json = serde_json::json!({
+            "status": "UP",
+            "components": {
+                "db": {
+                    "status": "UP",
+                    "details": {
+                        "duration_ms": 0
+                    },
+                    "components": {
+                        "primary": { "status": "UP" },
+                        "replica": { "status": "UP" }
+                    }
+                }
+            }
+        });
+
+        assert_eq!(serde_json::to_value(health.details().await).unwrap(), json);
+    }
+}
@@ -1,21 +1,170 @@
+use crate::database::DatabaseKind;
+
+/// Selects an SQL expression as text.
+#[cfg(any(feature = "diesel-r2d2", feature = "_diesel-async"))]
+type TextQuery = diesel::dsl::select<diesel::expression::SqlLiteral<diesel::sql_types::Text>>;
+
+/// Tells which database a diesel backend talks to, so [DatabaseMetadata](crate::database::DatabaseMetadata)
+/// can be implemented for the pools of its connections. It is implemented for the backends
+/// enabled by the `diesel-postgres`, `diesel-mysql` and `diesel-sqlite` features.
+pub trait DieselBackend {
+    fn kind() -> DatabaseKind;
+}
+
+#[cfg(feature = "diesel-postgres")]
+impl DieselBackend for diesel::pg::Pg {
+    fn kind() -> DatabaseKind {
+        DatabaseKind::Postgres
+    }
+}
+
+#[cfg(feature = "diesel-mysql")]
+impl DieselBackend for diesel::mysql::Mysql {
+    fn kind() -> DatabaseKind {
+        DatabaseKind::MySql
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl DieselBackend for diesel::sqlite::Sqlite {
+    fn kind() -> DatabaseKind {
+        DatabaseKind::Sqlite
+    }
+}
+
+/// The query to select the versions of the applied migrations.
+#[cfg(all(
+    feature = "diesel-migrations",
+    any(feature = "diesel-r2d2", feature = "_diesel-async")
+))]
+fn versions_query() -> TextQuery {
+    text_query(&format!("version FROM {}", migrations::MIGRATIONS_TABLE))
+}
+
+/// The query to select `expression` as text.
+#[cfg(any(feature = "diesel-r2d2", feature = "_diesel-async"))]
+fn text_query(expression: &str) -> TextQuery {
+    diesel::select(diesel::dsl::sql::<diesel::sql_types::Text>(expression))
+}
+
+// diesel-async only implements its pool managers for connections it can ping, so every impl
+// repeats the bounds of the ping.
+#[cfg(feature = "_diesel-async")]
 macro_rules! async_ping_impl {
-    ($pool:tt) => {
+    ($pool:tt, |$this:ident| $statistics:expr) => {
         #[async_trait::async_trait]
-        impl<Conn> crate::database::Pingable for $pool<Conn>
+        impl<Conn> crate::database::TryPingable for $pool<Conn>
         where
             Conn: diesel_async::pooled_connection::PoolableConnection + Send + 'static,
             diesel::dsl::select<diesel::dsl::AsExprOf<i32, diesel::sql_types::Integer>>:
                 diesel_async::methods::ExecuteDsl<Conn>,
             diesel::query_builder::SqlQuery: diesel::query_builder::QueryFragment<Conn::Backend>,
         {
-            async fn ping(&self) -> bool {
-                match self.get().await {
-                    Ok(mut conn) => conn
-                        .ping(&diesel_async::pooled_connection::RecyclingMethod::Verified)
-                        .await
-                        .is_ok(),
-                    Err(_) => false,
-                }
+            async fn try_ping(&self) -> Result<(), crate::database::PingError> {
+                let mut conn = self.get().await?;
+                conn.ping(&diesel_async::pooled_connection::RecyclingMethod::Verified)
+                    .await?;
+                Ok(())
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl<Conn> crate::database::ValidationQuery for $pool<Conn>
+        where
+            Conn: diesel_async::pooled_connection::PoolableConnection + Send + 'static,
+            diesel::dsl::select<diesel::dsl::AsExprOf<i32, diesel::sql_types::Integer>>:
+                diesel_async::methods::ExecuteDsl<Conn>,
+            diesel::query_builder::SqlQuery: diesel::query_builder::QueryFragment<Conn::Backend>,
+        {
+            // diesel can not load rows of an unknown shape, so only the success is reported.
+            async fn validate(
+                &self,
+                query: &str,
+            ) -> Result<Option<u64>, crate::database::PingError> {
+                let mut conn = self.get().await?;
+                diesel_async::SimpleAsyncConnection::batch_execute(&mut *conn, query).await?;
+                Ok(None)
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl<Conn> crate::database::PoolState for $pool<Conn>
+        where
+            Conn: diesel_async::pooled_connection::PoolableConnection + Send + 'static,
+            diesel::dsl::select<diesel::dsl::AsExprOf<i32, diesel::sql_types::Integer>>:
+                diesel_async::methods::ExecuteDsl<Conn>,
+            diesel::query_builder::SqlQuery: diesel::query_builder::QueryFragment<Conn::Backend>,
+        {
+            async fn statistics(&self) -> crate::database::PoolStatistics {
+                let $this = self;
+                $statistics
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl<Conn> crate::database::SelectText for $pool<Conn>
+        where
+            Conn: diesel_async::pooled_connection::PoolableConnection + Send + 'static,
+            diesel::dsl::select<diesel::dsl::AsExprOf<i32, diesel::sql_types::Integer>>:
+                diesel_async::methods::ExecuteDsl<Conn>,
+            diesel::query_builder::SqlQuery: diesel::query_builder::QueryFragment<Conn::Backend>,
+            for<'a> crate::database::diesel::TextQuery:
+                diesel_async::methods::LoadQuery<'a, Conn, String>,
+        {
+            async fn select_text(
+                &self,
+                expression: &str,
+            ) -> Result<String, crate::database::PingError> {
+                let mut conn = self.get().await?;
+                let query = crate::database::diesel::text_query(expression);
+                Ok(diesel_async::RunQueryDsl::get_result(query, &mut *conn).await?)
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl<Conn> crate::database::DatabaseMetadata for $pool<Conn>
+        where
+            Conn: diesel_async::pooled_connection::PoolableConnection + Send + 'static,
+            diesel::dsl::select<diesel::dsl::AsExprOf<i32, diesel::sql_types::Integer>>:
+                diesel_async::methods::ExecuteDsl<Conn>,
+            diesel::query_builder::SqlQuery: diesel::query_builder::QueryFragment<Conn::Backend>,
+            Conn::Backend: crate::database::diesel::DieselBackend,
+            Self: crate::database::SelectText,
+        {
+            async fn database_info(
+                &self,
+            ) -> Result<crate::database::DatabaseInfo, crate::database::PingError> {
+                use crate::database::SelectText;
+
+                let kind = <Conn::Backend as crate::database::diesel::DieselBackend>::kind();
+                let version = match kind.version_expression() {
+                    Some(expression) => Some(self.select_text(expression).await?),
+                    None => None,
+                };
+
+                Ok(crate::database::DatabaseInfo { kind, version })
+            }
+        }
+
+        #[cfg(feature = "diesel-migrations")]
+        #[async_trait::async_trait]
+        impl<Conn> crate::database::diesel::AppliedMigrations for $pool<Conn>
+        where
+            Conn: diesel_async::pooled_connection::PoolableConnection + Send + 'static,
+            diesel::dsl::select<diesel::dsl::AsExprOf<i32, diesel::sql_types::Integer>>:
+                diesel_async::methods::ExecuteDsl<Conn>,
+            diesel::query_builder::SqlQuery: diesel::query_builder::QueryFragment<Conn::Backend>,
+            Conn::Backend: crate::database::diesel::DieselBackend,
+            Self: crate::database::SelectText,
+            for<'a> crate::database::diesel::TextQuery:
+                diesel_async::methods::LoadQuery<'a, Conn, String>,
+        {
+            type Backend = Conn::Backend;
+
+            async fn applied_versions(&self) -> Result<Vec<String>, crate::database::PingError> {
+                let mut conn = self.get().await?;
+                let query = crate::database::diesel::versions_query();
+                Ok(diesel_async::RunQueryDsl::load(query, &mut *conn).await?)
             }
         }
     };
@@ -23,36 +172,404 @@ macro_rules! async_ping_impl {
 
 #[cfg(feature = "diesel-r2d2")]
 mod r2d2 {
-    use diesel::r2d2::{ConnectionManager, Pool};
-    #[async_trait::async_trait]
-    impl<Conn> crate::database::Pingable for Pool<ConnectionManager<Conn>>
+    use crate::database::PingError;
+    use diesel::r2d2::{ConnectionManager, Pool, R2D2Connection};
+    use futures::future::BoxFuture;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::{Mutex, OwnedMutexGuard};
+    use tokio::time::Instant;
+
+    /// Runs the queries of a check with a connection of an r2d2 pool.
+    trait WithConnection {
+        type Conn: R2D2Connection + Send + 'static;
+
+        fn pool(&self) -> &Pool<ConnectionManager<Self::Conn>>;
+
+        fn with_connection<T, F>(&self, f: F) -> BoxFuture<'_, Result<T, PingError>>
+        where
+            T: Send + 'static,
+            F: FnOnce(&mut Self::Conn) -> Result<T, PingError> + Send + 'static;
+    }
+
+    /// r2d2 is synchronous, so the checkout and `f` run on the blocking thread pool to keep a slow
+    /// or exhausted pool from stalling a runtime worker. `f` itself has no timeout, so the check
+    /// gives up at `deadline` and the blocking thread finishes in the background.
+    async fn run_blocking<Conn, T, F>(
+        pool: Pool<ConnectionManager<Conn>>,
+        timeout: Duration,
+        deadline: Instant,
+        f: F,
+    ) -> Result<T, PingError>
+    where
+        Conn: R2D2Connection + Send + 'static,
+        T: Send + 'static,
+        F: FnOnce(&mut Conn) -> Result<T, PingError> + Send + 'static,
+    {
+        let task = tokio::task::spawn_blocking(move || -> Result<T, PingError> {
+            let mut conn = pool.get_timeout(timeout)?;
+            f(&mut conn)
+        });
+        match tokio::time::timeout_at(deadline, task).await {
+            Ok(result) => result?,
+            Err(_) => Err("timed out waiting for the connection".into()),
+        }
+    }
+
+    // The connection timeout of the pool bounds the checkout, and twice as much the whole check.
+    impl<Conn> WithConnection for Pool<ConnectionManager<Conn>>
     where
-        Conn: diesel::r2d2::R2D2Connection + Send + 'static,
-        Conn::Backend: diesel::backend::DieselReserveSpecialization,
+        Conn: R2D2Connection + Send + 'static,
     {
-        async fn ping(&self) -> bool {
-            match self.get() {
-                Ok(mut conn) => conn.ping().is_ok(),
-                Err(_) => false,
+        type Conn = Conn;
+
+        fn pool(&self) -> &Pool<ConnectionManager<Conn>> {
+            self
+        }
+
+        fn with_connection<T, F>(&self, f: F) -> BoxFuture<'_, Result<T, PingError>>
+        where
+            T: Send + 'static,
+            F: FnOnce(&mut Conn) -> Result<T, PingError> + Send + 'static,
+        {
+            let timeout = self.connection_timeout();
+            let deadline = Instant::now() + timeout * 2;
+            Box::pin(run_blocking(self.clone(), timeout, deadline, f))
+        }
+    }
+
+    /// An r2d2 pool checked with a checkout timeout of its own, instead of the connection timeout
+    /// of the pool.
+    ///
+    /// Overlapping checks wait for each other rather than each taking a connection and a blocking
+    /// thread. A check that outlives its deadline keeps both until it finishes, the checks started
+    /// in the meantime fail right away instead of piling up against a hung database.
+    pub struct R2d2Pool<Conn>
+    where
+        Conn: R2D2Connection + 'static,
+    {
+        pool: Pool<ConnectionManager<Conn>>,
+        checkout_timeout: Duration,
+        running: Arc<Mutex<()>>,
+        /// The deadline of the running check, if any.
+        deadline: Arc<std::sync::Mutex<Option<Instant>>>,
+    }
+
+    impl<Conn> R2d2Pool<Conn>
+    where
+        Conn: R2D2Connection + 'static,
+    {
+        pub fn new(pool: Pool<ConnectionManager<Conn>>) -> Self {
+            R2d2Pool {
+                pool,
+                checkout_timeout: Duration::from_secs(5),
+                running: Arc::new(Mutex::new(())),
+                deadline: Arc::new(std::sync::Mutex::new(None)),
+            }
+        }
+
+        /// The longest a check waits for a connection, regardless of the connection timeout of
+        /// the pool, defaults to 5 seconds. The check is abandoned after twice this timeout.
+        pub fn with_checkout_timeout(mut self, timeout: Duration) -> Self {
+            self.checkout_timeout = timeout;
+            self
+        }
+    }
+
+    impl<Conn> From<Pool<ConnectionManager<Conn>>> for R2d2Pool<Conn>
+    where
+        Conn: R2D2Connection + 'static,
+    {
+        fn from(pool: Pool<ConnectionManager<Conn>>) -> Self {
+            R2d2Pool::new(pool)
+        }
+    }
+
+    /// Held by the running check until its blocking thread finishes.
+    struct Running {
+        _guard: OwnedMutexGuard<()>,
+        deadline: Arc<std::sync::Mutex<Option<Instant>>>,
+    }
+
+    impl Drop for Running {
+        fn drop(&mut self) {
+            *self
+                .deadline
+                .lock()
+                .unwrap_or_else(|error| error.into_inner()) = None;
+        }
+    }
+
+    impl<Conn> WithConnection for R2d2Pool<Conn>
+    where
+        Conn: R2D2Connection + Send + 'static,
+    {
+        type Conn = Conn;
+
+        fn pool(&self) -> &Pool<ConnectionManager<Conn>> {
+            &self.pool
+        }
+
+        fn with_connection<T, F>(&self, f: F) -> BoxFuture<'_, Result<T, PingError>>
+        where
+            T: Send + 'static,
+            F: FnOnce(&mut Conn) -> Result<T, PingError> + Send + 'static,
+        {
+            Box::pin(async move {
+                let deadline = Instant::now() + self.checkout_timeout * 2;
+                let abandoned = self
+                    .deadline
+                    .lock()
+                    .unwrap_or_else(|error| error.into_inner())
+                    .is_some_and(|running| running <= Instant::now());
+                if abandoned {
+                    return Err("the previous check is still running".into());
+                }
+
+                let guard = tokio::time::timeout_at(deadline, self.running.clone().lock_owned())
+                    .await
+                    .map_err(|_| "timed out waiting for the previous check")?;
+                *self
+                    .deadline
+                    .lock()
+                    .unwrap_or_else(|error| error.into_inner()) = Some(deadline);
+                let running = Running {
+                    _guard: guard,
+                    deadline: self.deadline.clone(),
+                };
+
+                // The time spent waiting for the previous check is taken from the checkout.
+                let timeout = self
+                    .checkout_timeout
+                    .min(deadline.saturating_duration_since(Instant::now()));
+                run_blocking(self.pool.clone(), timeout, deadline, move |conn| {
+                    let _running = running;
+                    f(conn)
+                })
+                .await
+            })
+        }
+    }
+
+    macro_rules! r2d2_ping_impl {
+        ($pool:ty) => {
+            #[async_trait::async_trait]
+            impl<Conn> crate::database::TryPingable for $pool
+            where
+                Conn: R2D2Connection + Send + 'static,
+            {
+                async fn try_ping(&self) -> Result<(), PingError> {
+                    self.with_connection(|conn| Ok(conn.ping()?)).await
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<Conn> crate::database::ValidationQuery for $pool
+            where
+                Conn: R2D2Connection + Send + 'static,
+            {
+                // diesel can not load rows of an unknown shape, so only the success is reported.
+                async fn validate(&self, query: &str) -> Result<Option<u64>, PingError> {
+                    let query = query.to_owned();
+                    self.with_connection(move |conn| {
+                        conn.batch_execute(&query)?;
+                        Ok(None)
+                    })
+                    .await
+                }
             }
+
+            #[async_trait::async_trait]
+            impl<Conn> crate::database::PoolState for $pool
+            where
+                Conn: R2D2Connection + Send + 'static,
+            {
+                async fn statistics(&self) -> crate::database::PoolStatistics {
+                    let pool = WithConnection::pool(self);
+                    let state = pool.state();
+                    crate::database::PoolStatistics {
+                        size: state.connections,
+                        idle: state.idle_connections,
+                        in_use: state.connections.saturating_sub(state.idle_connections),
+                        max_size: Some(pool.max_size()),
+                    }
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<Conn> crate::database::SelectText for $pool
+            where
+                Conn: R2D2Connection + Send + 'static,
+                for<'a> super::TextQuery: diesel::query_dsl::LoadQuery<'a, Conn, String>,
+            {
+                async fn select_text(&self, expression: &str) -> Result<String, PingError> {
+                    let query = super::text_query(expression);
+                    self.with_connection(move |conn| {
+                        Ok(diesel::RunQueryDsl::get_result(query, conn)?)
+                    })
+                    .await
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<Conn> crate::database::DatabaseMetadata for $pool
+            where
+                Conn: R2D2Connection + Send + 'static,
+                Conn::Backend: super::DieselBackend,
+                Self: crate::database::SelectText,
+            {
+                async fn database_info(&self) -> Result<crate::database::DatabaseInfo, PingError> {
+                    use crate::database::SelectText;
+
+                    let kind = <Conn::Backend as super::DieselBackend>::kind();
+                    let version = match kind.version_expression() {
+                        Some(expression) => Some(self.select_text(expression).await?),
+                        None => None,
+                    };
+
+                    Ok(crate::database::DatabaseInfo { kind, version })
+                }
+            }
+
+            #[cfg(feature = "diesel-migrations")]
+            #[async_trait::async_trait]
+            impl<Conn> super::AppliedMigrations for $pool
+            where
+                Conn: R2D2Connection + Send + 'static,
+                Conn::Backend: super::DieselBackend,
+                Self: crate::database::SelectText,
+                for<'a> super::TextQuery: diesel::query_dsl::LoadQuery<'a, Conn, String>,
+            {
+                type Backend = Conn::Backend;
+
+                async fn applied_versions(&self) -> Result<Vec<String>, PingError> {
+                    let query = super::versions_query();
+                    self.with_connection(move |conn| Ok(diesel::RunQueryDsl::load(query, conn)?))
+                        .await
+                }
+            }
+        };
+    }
+
+    r2d2_ping_impl!(Pool<ConnectionManager<Conn>>);
+    r2d2_ping_impl!(R2d2Pool<Conn>);
+}
+
+#[cfg(feature = "diesel-migrations")]
+mod migrations {
+    use super::DieselBackend;
+    use crate::database::migration::table_exists;
+    use crate::database::{PendingMigrations, PingError, SelectText};
+    use async_trait::async_trait;
+    use diesel::backend::Backend;
+    use diesel::migration::MigrationSource;
+    use diesel_migrations::EmbeddedMigrations;
+    use std::collections::HashSet;
+
+    /// The table diesel records the applied migrations in.
+    pub(super) const MIGRATIONS_TABLE: &str = "__diesel_schema_migrations";
+
+    /// Reads the migrations table of a pool, implemented for the pools of the connections with a
+    /// [DieselBackend] behind the `diesel-r2d2`, `diesel-deadpool`, `diesel-bb8` and `diesel-mobc`
+    /// features.
+    #[async_trait]
+    pub trait AppliedMigrations: SelectText {
+        type Backend: Backend + DieselBackend;
+
+        /// The versions in the migrations table, which must exist.
+        async fn applied_versions(&self) -> Result<Vec<String>, PingError>;
+    }
+
+    /// The migrations of a [MigrationSource], usually `diesel_migrations::embed_migrations!()`,
+    /// checked against a pool.
+    pub struct DieselMigrations<Pool, Source = EmbeddedMigrations>
+    where
+        Pool: AppliedMigrations,
+    {
+        source: Source,
+        pool: Pool,
+    }
+
+    impl<Pool, Source> DieselMigrations<Pool, Source>
+    where
+        Pool: AppliedMigrations,
+    {
+        pub fn new(source: Source, pool: Pool) -> Self {
+            DieselMigrations { source, pool }
+        }
+    }
+
+    #[async_trait]
+    impl<Pool, Source> PendingMigrations for DieselMigrations<Pool, Source>
+    where
+        Pool: AppliedMigrations + Send + Sync + 'static,
+        Source: MigrationSource<Pool::Backend> + Send + Sync + 'static,
+    {
+        async fn pending_migrations(&self) -> Result<Vec<String>, PingError> {
+            let kind = <Pool::Backend as DieselBackend>::kind();
+            let mut applied = HashSet::new();
+            if table_exists(&self.pool, &kind, MIGRATIONS_TABLE).await? {
+                applied.extend(self.pool.applied_versions().await?);
+            }
+
+            Ok(self
+                .source
+                .migrations()?
+                .iter()
+                .filter(|migration| !applied.contains(&migration.name().version().to_string()))
+                .map(|migration| migration.name().to_string())
+                .collect())
         }
     }
 }
 
+#[cfg(feature = "diesel-migrations")]
+pub use migrations::{AppliedMigrations, DieselMigrations};
+#[cfg(feature = "diesel-r2d2")]
+pub use r2d2::R2d2Pool;
+
 #[cfg(feature = "diesel-bb8")]
 mod bb8 {
+    use crate::database::PoolStatistics;
     use diesel_async::pooled_connection::bb8::Pool;
-    async_ping_impl!(Pool);
+    // bb8 does not expose the maximum size of the pool.
+    async_ping_impl!(Pool, |pool| {
+        let state = pool.state();
+        PoolStatistics {
+            size: state.connections,
+            idle: state.idle_connections,
+            in_use: state.connections.saturating_sub(state.idle_connections),
+            max_size: None,
+        }
+    });
 }
 
 #[cfg(feature = "diesel-deadpool")]
 mod deadpool {
+    use crate::database::PoolStatistics;
     use diesel_async::pooled_connection::deadpool::Pool;
-    async_ping_impl!(Pool);
+    async_ping_impl!(Pool, |pool| {
+        let status = pool.status();
+        PoolStatistics {
+            size: status.size as u32,
+            idle: status.available as u32,
+            in_use: status.size.saturating_sub(status.available) as u32,
+            max_size: Some(status.max_size as u32),
+        }
+    });
 }
 
 #[cfg(feature = "diesel-mobc")]
 mod mobc {
+    use crate::database::PoolStatistics;
     use diesel_async::pooled_connection::mobc::Pool;
-    async_ping_impl!(Pool);
+    async_ping_impl!(Pool, |pool| {
+        let state = pool.state().await;
+        PoolStatistics {
+            size: state.connections as u32,
+            idle: state.idle as u32,
+            in_use: state.in_use as u32,
+            max_size: Some(state.max_open as u32),
+        }
+    });
 }
@@ -0,0 +1,124 @@
+use crate::database::PingError;
+use crate::{HealthDetail, HealthIndicator, HealthStatus};
+use async_trait::async_trait;
+
+/// [MigrationHealthIndicator] can be used with anything that implements this trait, the sqlx,
+/// diesel and sea-orm migrators are supported behind the `sqlx-migrate`, `diesel-migrations` and
+/// `sea-orm-migration` features.
+///
+/// Unlike the migrators themselves, the implementations only read the migrations table and never
+/// create it, every migration is pending on a database without one.
+#[async_trait]
+pub trait PendingMigrations {
+    /// The names of the embedded migrations that have not been applied to the database.
+    async fn pending_migrations(&self) -> Result<Vec<String>, PingError>;
+}
+
+/// Whether the migrations `table` exists, checked before reading it since a migrator would
+/// create it instead.
+#[cfg(any(
+    feature = "sqlx-migrate",
+    feature = "diesel-migrations",
+    feature = "sea-orm-migration"
+))]
+pub(crate) async fn table_exists<Pool>(
+    pool: &Pool,
+    kind: &crate::database::DatabaseKind,
+    table: &str,
+) -> Result<bool, PingError>
+where
+    Pool: crate::database::SelectText + Sync,
+{
+    match kind.table_exists_expression(table) {
+        Some(expression) => Ok(pool.select_text(&expression).await? == "1"),
+        None => Err(format!("the migrations of {kind} databases can not be checked").into()),
+    }
+}
+
+/// Compares the migrations applied to the database against the embedded ones, reporting
+/// [HealthStatus::OutOfService] and the names of the missing migrations in the `pending` detail
+/// while the schema is behind.
+pub struct MigrationHealthIndicator<Migrations>
+where
+    Migrations: PendingMigrations,
+{
+    name: String,
+    migrations: Migrations,
+}
+
+impl<Migrations> MigrationHealthIndicator<Migrations>
+where
+    Migrations: PendingMigrations,
+{
+    pub fn new(name: String, migrations: Migrations) -> Self {
+        MigrationHealthIndicator { name, migrations }
+    }
+}
+
+#[async_trait]
+impl<Migrations> HealthIndicator for MigrationHealthIndicator<Migrations>
+where
+    Migrations: PendingMigrations + Send + Sync + 'static,
+{
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        match self.migrations.pending_migrations().await {
+            Ok(pending) if pending.is_empty() => HealthDetail::up(),
+            Ok(pending) => {
+                let mut detail = HealthDetail::new(HealthStatus::OutOfService);
+                detail.with_detail("pending".to_owned(), pending);
+                detail
+            }
+            Err(error) => {
+                let mut detail = HealthDetail::down();
+                detail.with_detail("error".to_owned(), error.to_string());
+                detail
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::database::{MigrationHealthIndicator, PendingMigrations, PingError};
+    use crate::{HealthDetail, HealthIndicator, HealthStatus};
+    use async_trait::async_trait;
+
+    struct MockMigrations(Result<Vec<&'static str>, &'static str>);
+
+    #[async_trait]
+    impl PendingMigrations for MockMigrations {
+        async fn pending_migrations(&self) -> Result<Vec<String>, PingError> {
+            match &self.0 {
+                Ok(pending) => Ok(pending.iter().map(|name| name.to_string()).collect()),
+                Err(error) => Err((*error).into()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrations() {
+        let indicator =
+            MigrationHealthIndicator::new("migrations".to_owned(), MockMigrations(Ok(vec![])));
+        assert_eq!(indicator.details().await, HealthDetail::up());
+
+        let indicator = MigrationHealthIndicator::new(
+            "migrations".to_owned(),
+            MockMigrations(Ok(vec!["2_add_users", "3_add_orders"])),
+        );
+        let mut expected = HealthDetail::new(HealthStatus::OutOfService);
+        expected.with_detail("pending".to_owned(), vec!["2_add_users", "3_add_orders"]);
+        assert_eq!(indicator.details().await, expected);
+
+        let indicator = MigrationHealthIndicator::new(
+            "migrations".to_owned(),
+            MockMigrations(Err("no such table")),
+        );
+        let mut expected = HealthDetail::down();
+        expected.with_detail("error".to_owned(), "no such table");
+        assert_eq!(indicator.details().await, expected);
+    }
+}
@@ -1,53 +1,505 @@
-use crate::{HealthDetail, HealthIndicator};
+use crate::{HealthDetail, HealthIndicator, HealthStatus};
 use async_trait::async_trait;
+use futures::future::BoxFuture;
+use serde::Serialize;
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::OnceCell;
 
 #[cfg(feature = "_diesel")]
 pub mod diesel;
+mod migration;
+#[cfg(feature = "_redis")]
+pub mod redis;
+mod replication;
 #[cfg(feature = "sea-orm")]
 pub mod sea_orm;
+#[cfg(feature = "_sqlite")]
+mod sqlite;
 #[cfg(feature = "sqlx")]
 pub mod sqlx;
 
+#[cfg(feature = "_redis")]
+pub use self::redis::{RedisHealthIndicator, RedisInfo};
+pub use migration::{MigrationHealthIndicator, PendingMigrations};
+pub use replication::{ReplicationLag, ReplicationLagHealthIndicator};
+#[cfg(feature = "_sqlite")]
+pub use sqlite::SqliteHealthIndicator;
+
+/// The cause of a failed ping, reported in the `error` detail of a [DatabaseHealthIndicator].
+pub type PingError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// A simpler form of [TryPingable] that only reports whether the ping succeeded.
+/// Every [Pingable] is also [TryPingable], failing with a generic error.
+#[async_trait]
+pub trait Pingable {
+    async fn ping(&self) -> bool;
+}
+
 /// [DatabaseHealthIndicator] can be used with anything that implements this trait.
 /// [diesel], [sea-orm], and [sqlx] all implement some form of a `ping` operation on their connection
 /// or connection pools, but this can be implemented for other database drivers using a manual query,
 /// generally a `SELECT 1` query or variant.
 #[async_trait]
-pub trait Pingable {
-    async fn ping(&self) -> bool;
+pub trait TryPingable {
+    async fn try_ping(&self) -> Result<(), PingError>;
+}
+
+/// Runs the query of [DatabaseHealthIndicator::with_validation_query].
+#[async_trait]
+pub trait ValidationQuery {
+    /// Runs `query` instead of the built-in ping, returning the number of rows it returned if the
+    /// driver reports it.
+    async fn validate(&self, query: &str) -> Result<Option<u64>, PingError>;
+}
+
+/// A connection pool exposing its state, see [DatabaseHealthIndicator::with_pool_statistics].
+#[async_trait]
+pub trait PoolState {
+    async fn statistics(&self) -> PoolStatistics;
 }
 
+/// Evaluates SQL expressions, used by `SqliteHealthIndicator`, to detect the server version and to
+/// look up the migrations table.
+#[async_trait]
+pub trait SelectText {
+    /// Evaluates an SQL expression, for example `sqlite_version()`, returning its value as text.
+    async fn select_text(&self, expression: &str) -> Result<String, PingError>;
+}
+
+/// Detects the database behind a pool, see [DatabaseHealthIndicator::with_database_info].
+#[async_trait]
+pub trait DatabaseMetadata {
+    /// Detects the kind and version of the database server.
+    async fn database_info(&self) -> Result<DatabaseInfo, PingError>;
+}
+
+/// The kind of database server, displayed the way Spring reports it, e.g. `PostgreSQL`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum DatabaseKind {
+    Postgres,
+    MySql,
+    Sqlite,
+    Other(String),
+}
+
+impl DatabaseKind {
+    /// An SQL expression evaluating to the server version as text.
+    pub fn version_expression(&self) -> Option<&'static str> {
+        match self {
+            DatabaseKind::Postgres => Some("current_setting('server_version')"),
+            DatabaseKind::MySql => Some("VERSION()"),
+            DatabaseKind::Sqlite => Some("sqlite_version()"),
+            DatabaseKind::Other(_) => None,
+        }
+    }
+
+    /// An SQL expression evaluating to `'1'` when `table` exists and to `'0'` otherwise, read from
+    /// the catalog so the table itself is never queried.
+    pub fn table_exists_expression(&self, table: &str) -> Option<String> {
+        let table = table.replace('\'', "''");
+        match self {
+            DatabaseKind::Postgres => Some(format!(
+                "CASE WHEN to_regclass('{table}') IS NULL THEN '0' ELSE '1' END"
+            )),
+            DatabaseKind::MySql => Some(format!(
+                "(SELECT CAST(COUNT(*) AS CHAR) FROM information_schema.tables \
+                WHERE table_schema = DATABASE() AND table_name = '{table}')"
+            )),
+            DatabaseKind::Sqlite => Some(format!(
+                "(SELECT CAST(COUNT(*) AS TEXT) FROM sqlite_master \
+                WHERE type = 'table' AND name = '{table}')"
+            )),
+            DatabaseKind::Other(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for DatabaseKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseKind::Postgres => f.write_str("PostgreSQL"),
+            DatabaseKind::MySql => f.write_str("MySQL"),
+            DatabaseKind::Sqlite => f.write_str("SQLite"),
+            DatabaseKind::Other(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Reported in the `database` and `version` details of a [DatabaseHealthIndicator].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DatabaseInfo {
+    pub kind: DatabaseKind,
+    pub version: Option<String>,
+}
+
+/// The state of a connection pool, reported in the `pool` detail of a [DatabaseHealthIndicator].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub struct PoolStatistics {
+    /// The number of open connections, both idle and in use.
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    /// The maximum number of connections, if the pool exposes it.
+    pub max_size: Option<u32>,
+}
+
+impl PoolStatistics {
+    /// The fraction of the maximum number of connections that is in use.
+    pub fn usage(&self) -> Option<f64> {
+        self.max_size
+            .filter(|max_size| *max_size > 0)
+            .map(|max_size| self.in_use as f64 / max_size as f64)
+    }
+}
+
+#[async_trait]
+impl<T> TryPingable for T
+where
+    T: Pingable + Sync,
+{
+    async fn try_ping(&self) -> Result<(), PingError> {
+        if self.ping().await {
+            Ok(())
+        } else {
+            Err("ping failed".into())
+        }
+    }
+}
+
+type Validate<Pool> =
+    for<'a> fn(&'a Pool, &'a str) -> BoxFuture<'a, Result<Option<u64>, PingError>>;
+type Statistics<Pool> = for<'a> fn(&'a Pool) -> BoxFuture<'a, PoolStatistics>;
+type DetectDatabase<Pool> = for<'a> fn(&'a Pool) -> BoxFuture<'a, Result<DatabaseInfo, PingError>>;
+
 pub struct DatabaseHealthIndicator<Pool>
 where
-    Pool: Pingable,
+    Pool: TryPingable,
 {
     name: String,
     pool: Pool,
+    // The optional checks are only available for pools implementing their trait, so they are
+    // captured as functions when enabled instead of bounding the indicator on every trait.
+    statistics: Option<Statistics<Pool>>,
+    usage_threshold: Option<(f64, HealthStatus)>,
+    latency_threshold: Option<(Duration, HealthStatus)>,
+    validation_query: Option<(String, Validate<Pool>)>,
+    database_info: Option<(OnceCell<DatabaseInfo>, DetectDatabase<Pool>)>,
 }
 
 impl<Pool> DatabaseHealthIndicator<Pool>
 where
-    Pool: Pingable,
+    Pool: TryPingable,
 {
     pub fn new(name: String, pool: Pool) -> Self {
-        DatabaseHealthIndicator { name, pool }
+        DatabaseHealthIndicator {
+            name,
+            pool,
+            statistics: None,
+            usage_threshold: None,
+            latency_threshold: None,
+            validation_query: None,
+            database_info: None,
+        }
+    }
+
+    /// Reports the kind and version of the database server in the details. They are detected on
+    /// the first successful check and cached afterwards, a failed detection is reported as
+    /// `database_info_error` and retried on the next check.
+    pub fn with_database_info(mut self) -> Self
+    where
+        Pool: DatabaseMetadata,
+    {
+        let detect: DetectDatabase<Pool> = |pool| pool.database_info();
+        self.database_info = Some((OnceCell::new(), detect));
+        self
+    }
+
+    /// Reports `status` instead of [HealthStatus::Up] when the ping, or the validation query, takes
+    /// longer than `threshold`.
+    pub fn with_latency_threshold(mut self, threshold: Duration, status: HealthStatus) -> Self {
+        self.latency_threshold = Some((threshold, status));
+        self
+    }
+
+    /// Checks the database by running `query` instead of pinging it, for example
+    /// `SELECT 1 FROM critical_table LIMIT 1`. The query and the number of rows it returned are
+    /// reported in the details.
+    pub fn with_validation_query(mut self, query: String) -> Self
+    where
+        Pool: ValidationQuery,
+    {
+        let validate: Validate<Pool> = |pool, query| pool.validate(query);
+        self.validation_query = Some((query, validate));
+        self
+    }
+
+    /// Reports the state of the connection pool in the `pool` detail.
+    pub fn with_pool_statistics(mut self) -> Self
+    where
+        Pool: PoolState,
+    {
+        let statistics: Statistics<Pool> = |pool| pool.statistics();
+        self.statistics = Some(statistics);
+        self
+    }
+
+    /// Reports `status` instead of [HealthStatus::Up] when the fraction of connections in use is
+    /// at or above `threshold`, for example `0.9` and `HealthStatus::Custom("DEGRADED")`.
+    /// Only applies to pools that expose their maximum size. Implies
+    /// [DatabaseHealthIndicator::with_pool_statistics].
+    pub fn with_usage_threshold(mut self, threshold: f64, status: HealthStatus) -> Self
+    where
+        Pool: PoolState,
+    {
+        self.usage_threshold = Some((threshold, status));
+        self.with_pool_statistics()
     }
 }
 
 #[async_trait]
 impl<Pool> HealthIndicator for DatabaseHealthIndicator<Pool>
 where
-    Pool: Pingable + Send + Sync + 'static,
+    Pool: TryPingable + Send + Sync + 'static,
 {
     fn name(&self) -> String {
         self.name.clone()
     }
 
     async fn details(&self) -> HealthDetail {
-        if self.pool.ping().await {
-            HealthDetail::up()
-        } else {
-            HealthDetail::down()
+        let start = tokio::time::Instant::now();
+        let result = match &self.validation_query {
+            Some((query, validate)) => validate(&self.pool, query).await,
+            None => self.pool.try_ping().await.map(|()| None),
+        };
+        let latency = start.elapsed();
+
+        let mut detail = match result {
+            Ok(rows) => {
+                let mut detail = HealthDetail::up();
+                if let Some(rows) = rows {
+                    detail.with_detail("rows".to_owned(), rows);
+                }
+                detail
+            }
+            Err(error) => {
+                let mut detail = HealthDetail::down();
+                detail.with_detail("error".to_owned(), error.to_string());
+                detail
+            }
+        };
+
+        if let Some((query, _)) = &self.validation_query {
+            detail.with_detail("validation_query".to_owned(), query.as_str());
+        }
+
+        if let Some((threshold, status)) = &self.latency_threshold {
+            if detail.status == HealthStatus::Up && latency > *threshold {
+                detail.status = status.clone();
+                detail.with_detail(
+                    "latency_threshold_ms".to_owned(),
+                    threshold.as_millis() as u64,
+                );
+            }
+        }
+
+        if let Some((database_info, detect)) = &self.database_info {
+            // A database that is down can not be detected, only a previous detection is reported.
+            let info = match database_info.get() {
+                Some(info) => Some(info),
+                None if detail.status == HealthStatus::Down => None,
+                None => match database_info.get_or_try_init(|| detect(&self.pool)).await {
+                    Ok(info) => Some(info),
+                    Err(error) => {
+                        detail.with_detail("database_info_error".to_owned(), error.to_string());
+                        None
+                    }
+                },
+            };
+            if let Some(info) = info {
+                detail.with_detail("database".to_owned(), info.kind.to_string());
+                if let Some(version) = &info.version {
+                    detail.with_detail("version".to_owned(), version.as_str());
+                }
+            }
+        }
+
+        if let Some(statistics) = &self.statistics {
+            let statistics = statistics(&self.pool).await;
+            if let (HealthStatus::Up, Some((threshold, status)), Some(usage)) =
+                (&detail.status, &self.usage_threshold, statistics.usage())
+            {
+                if usage >= *threshold {
+                    detail.status = status.clone();
+                    detail.with_detail("usage_threshold".to_owned(), *threshold);
+                }
+            }
+
+            // The statistics are plain integers, serializing them can not fail.
+            let _ = detail.with_serialized_detail("pool".to_owned(), &statistics);
+        }
+
+        detail
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::database::{
+        DatabaseHealthIndicator, DatabaseInfo, DatabaseKind, DatabaseMetadata, PingError, Pingable,
+        PoolState, PoolStatistics, TryPingable,
+    };
+    use crate::{HealthDetail, HealthIndicator, HealthStatus};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    struct MockPool(bool);
+
+    #[async_trait]
+    impl Pingable for MockPool {
+        async fn ping(&self) -> bool {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pingable_adapter() {
+        let indicator = DatabaseHealthIndicator::new("up".to_owned(), MockPool(true));
+        assert_eq!(indicator.details().await, HealthDetail::up());
+
+        let indicator = DatabaseHealthIndicator::new("down".to_owned(), MockPool(false));
+        let mut expected = HealthDetail::down();
+        expected.with_detail("error".to_owned(), "ping failed");
+        assert_eq!(indicator.details().await, expected);
+    }
+
+    struct MockPoolWithStatistics(PoolStatistics);
+
+    #[async_trait]
+    impl TryPingable for MockPoolWithStatistics {
+        async fn try_ping(&self) -> Result<(), PingError> {
+            Ok(())
         }
     }
+
+    #[async_trait]
+    impl PoolState for MockPoolWithStatistics {
+        async fn statistics(&self) -> PoolStatistics {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_usage_threshold() {
+        let degraded = HealthStatus::Custom("DEGRADED".to_owned());
+        let statistics = |in_use| PoolStatistics {
+            size: 10,
+            idle: 10 - in_use,
+            in_use,
+            max_size: Some(10),
+        };
+
+        let indicator =
+            DatabaseHealthIndicator::new("db".to_owned(), MockPoolWithStatistics(statistics(5)))
+                .with_usage_threshold(0.9, degraded.clone());
+        let detail = indicator.details().await;
+        assert_eq!(detail.status, HealthStatus::Up);
+        assert_eq!(
+            detail.details["pool"],
+            serde_json::json!({ "size": 10, "idle": 5, "in_use": 5, "max_size": 10 })
+        );
+        assert!(!detail.details.contains_key("usage_threshold"));
+
+        let indicator =
+            DatabaseHealthIndicator::new("db".to_owned(), MockPoolWithStatistics(statistics(9)))
+                .with_usage_threshold(0.9, degraded.clone());
+        let detail = indicator.details().await;
+        assert_eq!(detail.status, degraded);
+        assert_eq!(detail.details["usage_threshold"], 0.9);
+    }
+
+    struct SlowPool(Duration);
+
+    #[async_trait]
+    impl TryPingable for SlowPool {
+        async fn try_ping(&self) -> Result<(), PingError> {
+            tokio::time::sleep(self.0).await;
+            Ok(())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_latency_threshold() {
+        let degraded = HealthStatus::Custom("DEGRADED".to_owned());
+
+        let indicator =
+            DatabaseHealthIndicator::new("db".to_owned(), SlowPool(Duration::from_millis(50)))
+                .with_latency_threshold(Duration::from_millis(100), degraded.clone());
+        assert_eq!(indicator.details().await, HealthDetail::up());
+
+        let indicator =
+            DatabaseHealthIndicator::new("db".to_owned(), SlowPool(Duration::from_millis(150)))
+                .with_latency_threshold(Duration::from_millis(100), degraded.clone());
+        let detail = indicator.details().await;
+        assert_eq!(detail.status, degraded);
+        assert_eq!(detail.details["latency_threshold_ms"], 100);
+    }
+
+    /// A pool whose first detection fails.
+    struct MockDatabase {
+        up: bool,
+        detections: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl TryPingable for MockDatabase {
+        async fn try_ping(&self) -> Result<(), PingError> {
+            if self.up {
+                Ok(())
+            } else {
+                Err("connection refused".into())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DatabaseMetadata for MockDatabase {
+        async fn database_info(&self) -> Result<DatabaseInfo, PingError> {
+            match self.detections.fetch_add(1, Ordering::SeqCst) {
+                0 => Err("permission denied".into()),
+                _ => Ok(DatabaseInfo {
+                    kind: DatabaseKind::Postgres,
+                    version: Some("16.2".to_owned()),
+                }),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_database_info() {
+        let database = |up| MockDatabase {
+            up,
+            detections: AtomicUsize::new(0),
+        };
+
+        let indicator =
+            DatabaseHealthIndicator::new("db".to_owned(), database(false)).with_database_info();
+        let detail = indicator.details().await;
+        assert_eq!(detail.status, HealthStatus::Down);
+        assert!(!detail.details.contains_key("database_info_error"));
+        assert_eq!(indicator.pool.detections.load(Ordering::SeqCst), 0);
+
+        let indicator =
+            DatabaseHealthIndicator::new("db".to_owned(), database(true)).with_database_info();
+        let detail = indicator.details().await;
+        assert_eq!(detail.status, HealthStatus::Up);
+        assert_eq!(detail.details["database_info_error"], "permission denied");
+        assert!(!detail.details.contains_key("database"));
+
+        let detail = indicator.details().await;
+        assert_eq!(detail.details["database"], "PostgreSQL");
+        assert_eq!(detail.details["version"], "16.2");
+        assert!(!detail.details.contains_key("database_info_error"));
+    }
 }
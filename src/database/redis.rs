@@ -0,0 +1,232 @@
+use crate::database::{DatabaseHealthIndicator, PingError, TryPingable};
+use crate::{HealthDetail, HealthIndicator, HealthStatus};
+use async_trait::async_trait;
+
+/// Runs `INFO` on a Redis server. Implemented, along with [TryPingable], for the connections and
+/// pools behind the `redis`, `deadpool-redis`, `bb8-redis` and `mobc-redis` features.
+#[async_trait]
+pub trait RedisInfo {
+    async fn info(&self) -> Result<String, PingError>;
+}
+
+/// Pings a Redis server through a [DatabaseHealthIndicator], optionally reporting fields of its
+/// `INFO` reply such as `redis_version` or `connected_clients`.
+pub struct RedisHealthIndicator<Pool>
+where
+    Pool: TryPingable + RedisInfo,
+{
+    database: DatabaseHealthIndicator<Pool>,
+    info_fields: Vec<String>,
+}
+
+impl<Pool> RedisHealthIndicator<Pool>
+where
+    Pool: TryPingable + RedisInfo,
+{
+    pub fn new(name: String, pool: Pool) -> Self {
+        DatabaseHealthIndicator::new(name, pool).into()
+    }
+
+    /// Reports `field` of the `INFO` reply in the details, numbers are reported as numbers.
+    pub fn with_info_field(mut self, field: String) -> Self {
+        self.info_fields.push(field);
+        self
+    }
+}
+
+impl<Pool> From<DatabaseHealthIndicator<Pool>> for RedisHealthIndicator<Pool>
+where
+    Pool: TryPingable + RedisInfo,
+{
+    fn from(database: DatabaseHealthIndicator<Pool>) -> Self {
+        RedisHealthIndicator {
+            database,
+            info_fields: Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl<Pool> HealthIndicator for RedisHealthIndicator<Pool>
+where
+    Pool: TryPingable + RedisInfo + Send + Sync + 'static,
+{
+    fn name(&self) -> String {
+        self.database.name()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        let mut detail = self.database.details().await;
+        if detail.status == HealthStatus::Down || self.info_fields.is_empty() {
+            return detail;
+        }
+
+        match self.database.pool.info().await {
+            Ok(info) => {
+                for field in &self.info_fields {
+                    if let Some(value) = info_field(&info, field) {
+                        let value = match value.parse::<serde_json::Number>() {
+                            Ok(number) => number.into(),
+                            Err(_) => serde_json::Value::from(value),
+                        };
+                        detail.with_detail(field.clone(), value);
+                    }
+                }
+            }
+            Err(error) => {
+                detail.status = HealthStatus::Down;
+                detail.with_detail("error".to_owned(), error.to_string());
+            }
+        }
+        detail
+    }
+}
+
+/// Finds `field` in the `field:value` lines of an `INFO` reply.
+fn info_field<'a>(info: &'a str, field: &str) -> Option<&'a str> {
+    info.lines()
+        .filter_map(|line| line.trim_end().split_once(':'))
+        .find(|(name, _)| *name == field)
+        .map(|(_, value)| value)
+}
+
+macro_rules! redis_ping_impl {
+    ($pool:ty, |$this:ident| $connection:expr, |$conn:ident| $borrow:expr) => {
+        async fn query<T>($this: &$pool, command: &str) -> Result<T, crate::database::PingError>
+        where
+            T: redis::FromRedisValue,
+        {
+            let mut $conn = $connection;
+            Ok(redis::cmd(command).query_async::<T>($borrow).await?)
+        }
+
+        #[async_trait::async_trait]
+        impl crate::database::TryPingable for $pool {
+            async fn try_ping(&self) -> Result<(), crate::database::PingError> {
+                query::<String>(self, "PING").await?;
+                Ok(())
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl crate::database::DatabaseMetadata for $pool {
+            async fn database_info(
+                &self,
+            ) -> Result<crate::database::DatabaseInfo, crate::database::PingError> {
+                let info = query::<String>(self, "INFO").await?;
+                Ok(crate::database::DatabaseInfo {
+                    kind: crate::database::DatabaseKind::Other("Redis".to_owned()),
+                    version: crate::database::redis::info_field(&info, "redis_version")
+                        .map(str::to_owned),
+                })
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl crate::database::redis::RedisInfo for $pool {
+            async fn info(&self) -> Result<String, crate::database::PingError> {
+                query::<String>(self, "INFO").await
+            }
+        }
+    };
+}
+
+// Connections are multiplexed, so every check runs on a cheap clone.
+#[cfg(feature = "redis")]
+mod connection {
+    use redis::aio::{ConnectionManager, MultiplexedConnection};
+
+    mod manager {
+        use super::ConnectionManager;
+        redis_ping_impl!(ConnectionManager, |this| this.clone(), |conn| &mut conn);
+    }
+
+    mod multiplexed {
+        use super::MultiplexedConnection;
+        redis_ping_impl!(MultiplexedConnection, |this| this.clone(), |conn| &mut conn);
+    }
+}
+
+#[cfg(feature = "deadpool-redis")]
+mod deadpool {
+    use crate::database::{PoolState, PoolStatistics};
+    use deadpool_redis::{redis, Pool};
+    redis_ping_impl!(Pool, |this| this.get().await?, |conn| &mut conn);
+
+    #[async_trait::async_trait]
+    impl PoolState for Pool {
+        async fn statistics(&self) -> PoolStatistics {
+            let status = self.status();
+            PoolStatistics {
+                size: status.size as u32,
+                idle: status.available as u32,
+                in_use: status.size.saturating_sub(status.available) as u32,
+                max_size: Some(status.max_size as u32),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bb8-redis")]
+mod bb8 {
+    use crate::database::{PoolState, PoolStatistics};
+    use bb8_redis::bb8::Pool;
+    use bb8_redis::{redis, RedisConnectionManager};
+    redis_ping_impl!(
+        Pool<RedisConnectionManager>,
+        |this| this.get().await?,
+        |conn| &mut *conn
+    );
+
+    // bb8 does not expose the maximum size of the pool.
+    #[async_trait::async_trait]
+    impl PoolState for Pool<RedisConnectionManager> {
+        async fn statistics(&self) -> PoolStatistics {
+            let state = self.state();
+            PoolStatistics {
+                size: state.connections,
+                idle: state.idle_connections,
+                in_use: state.connections.saturating_sub(state.idle_connections),
+                max_size: None,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "mobc-redis")]
+mod mobc {
+    use crate::database::{PoolState, PoolStatistics};
+    use mobc_redis::mobc::Pool;
+    use mobc_redis::{redis, RedisConnectionManager};
+    redis_ping_impl!(
+        Pool<RedisConnectionManager>,
+        |this| this.get().await?,
+        |conn| &mut *conn
+    );
+
+    #[async_trait::async_trait]
+    impl PoolState for Pool<RedisConnectionManager> {
+        async fn statistics(&self) -> PoolStatistics {
+            let state = self.state().await;
+            PoolStatistics {
+                size: state.connections as u32,
+                idle: state.idle as u32,
+                in_use: state.in_use as u32,
+                max_size: Some(state.max_open as u32),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::database::redis::info_field;
+
+    #[test]
+    fn test_info_field() {
+        let info = "# Server\r\nredis_version:7.2.4\r\nredis_mode:standalone\r\n\r\n# Clients\r\nconnected_clients:3\r\n";
+        assert_eq!(info_field(info, "redis_version"), Some("7.2.4"));
+        assert_eq!(info_field(info, "connected_clients"), Some("3"));
+        assert_eq!(info_field(info, "used_memory"), None);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::database::PingError;
+use crate::threshold::Thresholds;
+use crate::{HealthDetail, HealthIndicator, HealthStatus};
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// [ReplicationLagHealthIndicator] can be used with anything that implements this trait, sqlx
+/// pools implement it for PostgreSQL and MySQL.
+#[async_trait]
+pub trait ReplicationLag {
+    /// How far the replica is behind its primary, failing if the server is not a replica or
+    /// replication is not running.
+    async fn replication_lag(&self) -> Result<Duration, PingError>;
+}
+
+/// Reports the replication lag of a read replica in the `lag_ms` detail.
+///
+/// ```rust
+/// # use axum_health::database::{ReplicationLag, ReplicationLagHealthIndicator};
+/// # use axum_health::HealthStatus;
+/// # use std::time::Duration;
+/// # fn indicator<Pool: ReplicationLag>(pool: Pool) -> ReplicationLagHealthIndicator<Pool> {
+/// ReplicationLagHealthIndicator::new("replica".to_owned(), pool)
+///     .with_threshold(Duration::from_secs(10), HealthStatus::degraded())
+///     .with_threshold(Duration::from_secs(60), HealthStatus::Down)
+/// # }
+/// ```
+pub struct ReplicationLagHealthIndicator<Pool>
+where
+    Pool: ReplicationLag,
+{
+    name: String,
+    pool: Pool,
+    thresholds: Thresholds<Duration>,
+}
+
+impl<Pool> ReplicationLagHealthIndicator<Pool>
+where
+    Pool: ReplicationLag,
+{
+    pub fn new(name: String, pool: Pool) -> Self {
+        ReplicationLagHealthIndicator {
+            name,
+            pool,
+            thresholds: Thresholds::new(),
+        }
+    }
+
+    /// Reports `status` instead of [HealthStatus::Up] when the lag is above `threshold`, the
+    /// status of the highest threshold exceeded applies.
+    pub fn with_threshold(mut self, threshold: Duration, status: HealthStatus) -> Self {
+        self.thresholds.push(threshold, status);
+        self
+    }
+}
+
+#[async_trait]
+impl<Pool> HealthIndicator for ReplicationLagHealthIndicator<Pool>
+where
+    Pool: ReplicationLag + Send + Sync + 'static,
+{
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        let lag = match self.pool.replication_lag().await {
+            Ok(lag) => lag,
+            Err(error) => {
+                let mut detail = HealthDetail::down();
+                detail.with_detail("error".to_owned(), error.to_string());
+                return detail;
+            }
+        };
+
+        let mut detail = HealthDetail::up();
+        detail.with_detail("lag_ms".to_owned(), lag.as_millis() as u64);
+        if let Some((threshold, status)) = self.thresholds.find(|threshold| lag > *threshold) {
+            detail.status = status.clone();
+            detail.with_detail("threshold_ms".to_owned(), threshold.as_millis() as u64);
+        }
+        detail
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::database::{PingError, ReplicationLag, ReplicationLagHealthIndicator};
+    use crate::{HealthDetail, HealthIndicator, HealthStatus};
+    use async_trait::async_trait;
+    use std::time::Duration;
+
+    struct MockReplica(Option<Duration>);
+
+    #[async_trait]
+    impl ReplicationLag for MockReplica {
+        async fn replication_lag(&self) -> Result<Duration, PingError> {
+            self.0.ok_or_else(|| "the server is not a replica".into())
+        }
+    }
+
+    fn indicator(lag: Option<Duration>) -> ReplicationLagHealthIndicator<MockReplica> {
+        ReplicationLagHealthIndicator::new("replica".to_owned(), MockReplica(lag))
+            .with_threshold(Duration::from_secs(60), HealthStatus::Down)
+            .with_threshold(Duration::from_secs(10), HealthStatus::degraded())
+    }
+
+    #[tokio::test]
+    async fn test_replication_lag() {
+        let mut expected = HealthDetail::up();
+        expected.with_detail("lag_ms".to_owned(), 1500);
+        assert_eq!(
+            indicator(Some(Duration::from_millis(1500))).details().await,
+            expected
+        );
+
+        let detail = indicator(Some(Duration::from_secs(30))).details().await;
+        assert_eq!(detail.status, HealthStatus::degraded());
+        assert_eq!(detail.details["threshold_ms"], 10_000);
+
+        let detail = indicator(Some(Duration::from_secs(90))).details().await;
+        assert_eq!(detail.status, HealthStatus::Down);
+        assert_eq!(detail.details["lag_ms"], 90_000);
+        assert_eq!(detail.details["threshold_ms"], 60_000);
+
+        let mut expected = HealthDetail::down();
+        expected.with_detail("error".to_owned(), "the server is not a replica");
+        assert_eq!(indicator(None).details().await, expected);
+    }
+}
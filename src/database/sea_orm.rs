@@ -1,10 +1,126 @@
-use crate::database::Pingable;
+use crate::database::{
+    DatabaseInfo, DatabaseKind, DatabaseMetadata, PingError, SelectText, TryPingable,
+    ValidationQuery,
+};
 use async_trait::async_trait;
-use sea_orm::DatabaseConnection;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
 
 #[async_trait]
-impl Pingable for DatabaseConnection {
-    async fn ping(&self) -> bool {
-        self.ping().await.is_ok()
+impl TryPingable for DatabaseConnection {
+    async fn try_ping(&self) -> Result<(), PingError> {
+        self.ping().await?;
+        Ok(())
     }
 }
+
+#[async_trait]
+impl ValidationQuery for DatabaseConnection {
+    async fn validate(&self, query: &str) -> Result<Option<u64>, PingError> {
+        let statement = Statement::from_string(self.get_database_backend(), query);
+        let rows = self.query_all(statement).await?;
+        Ok(Some(rows.len() as u64))
+    }
+}
+
+#[async_trait]
+impl SelectText for DatabaseConnection {
+    async fn select_text(&self, expression: &str) -> Result<String, PingError> {
+        let statement =
+            Statement::from_string(self.get_database_backend(), format!("SELECT {expression}"));
+        match self.query_one(statement).await? {
+            Some(row) => Ok(row.try_get_by_index::<String>(0)?),
+            None => Err("the query returned no rows".into()),
+        }
+    }
+}
+
+fn database_kind(backend: DatabaseBackend) -> DatabaseKind {
+    match backend {
+        DatabaseBackend::Postgres => DatabaseKind::Postgres,
+        DatabaseBackend::MySql => DatabaseKind::MySql,
+        DatabaseBackend::Sqlite => DatabaseKind::Sqlite,
+    }
+}
+
+#[async_trait]
+impl DatabaseMetadata for DatabaseConnection {
+    async fn database_info(&self) -> Result<DatabaseInfo, PingError> {
+        let kind = database_kind(self.get_database_backend());
+
+        let version = match kind.version_expression() {
+            Some(expression) => Some(self.select_text(expression).await?),
+            None => None,
+        };
+
+        Ok(DatabaseInfo { kind, version })
+    }
+}
+
+#[cfg(feature = "sea-orm-migration")]
+mod migration {
+    use crate::database::migration::table_exists;
+    use crate::database::{PendingMigrations, PingError};
+    use async_trait::async_trait;
+    use sea_orm::sea_query::Query;
+    use sea_orm::{ConnectionTrait, DatabaseConnection};
+    use sea_orm_migration::{seaql_migrations, MigratorTrait};
+    use std::collections::HashSet;
+    use std::marker::PhantomData;
+
+    /// The migrations of `Migrator` checked against a connection.
+    pub struct SeaOrmMigrations<Migrator>
+    where
+        Migrator: MigratorTrait,
+    {
+        database: DatabaseConnection,
+        migrator: PhantomData<fn() -> Migrator>,
+    }
+
+    impl<Migrator> SeaOrmMigrations<Migrator>
+    where
+        Migrator: MigratorTrait,
+    {
+        pub fn new(database: DatabaseConnection) -> Self {
+            SeaOrmMigrations {
+                database,
+                migrator: PhantomData,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl<Migrator> PendingMigrations for SeaOrmMigrations<Migrator>
+    where
+        Migrator: MigratorTrait,
+    {
+        async fn pending_migrations(&self) -> Result<Vec<String>, PingError> {
+            let backend = self.database.get_database_backend();
+            let table = Migrator::migration_table_name();
+            let mut applied = HashSet::new();
+            if table_exists(
+                &self.database,
+                &super::database_kind(backend),
+                &table.to_string(),
+            )
+            .await?
+            {
+                let query = Query::select()
+                    .column(seaql_migrations::Column::Version)
+                    .from(table)
+                    .to_owned();
+                for row in self.database.query_all(backend.build(&query)).await? {
+                    applied.insert(row.try_get::<String>("", "version")?);
+                }
+            }
+
+            Ok(Migrator::migrations()
+                .iter()
+                .map(|migration| migration.name().to_owned())
+                .filter(|name| !applied.contains(name))
+                .collect())
+        }
+    }
+}
+
+#[cfg(feature = "sea-orm-migration")]
+pub use migration::SeaOrmMigrations;
@@ -0,0 +1,181 @@
+use crate::database::{DatabaseHealthIndicator, PingError, SelectText, TryPingable};
+use crate::{HealthDetail, HealthIndicator, HealthStatus};
+use async_trait::async_trait;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Checks a SQLite database on top of the ping of a [DatabaseHealthIndicator], reporting its
+/// `journal_mode`, the `path` and `size_bytes` of the database file, the `wal_size_bytes` of its
+/// write-ahead log and whether the file is `read_only`. In-memory databases only report the
+/// journal mode.
+pub struct SqliteHealthIndicator<Pool>
+where
+    Pool: TryPingable + SelectText,
+{
+    database: DatabaseHealthIndicator<Pool>,
+    quick_check: bool,
+    read_only_status: Option<HealthStatus>,
+}
+
+impl<Pool> SqliteHealthIndicator<Pool>
+where
+    Pool: TryPingable + SelectText,
+{
+    pub fn new(name: String, pool: Pool) -> Self {
+        DatabaseHealthIndicator::new(name, pool).into()
+    }
+
+    /// Runs `PRAGMA quick_check` on every check, reporting [HealthStatus::Down] and the problems
+    /// it found unless it returns `ok`. This reads the whole database, so it can be slow on
+    /// large files.
+    pub fn with_quick_check(mut self) -> Self {
+        self.quick_check = true;
+        self
+    }
+
+    /// Reports `status` instead of [HealthStatus::Up] when the database file or its filesystem
+    /// is read-only.
+    pub fn with_read_only_status(mut self, status: HealthStatus) -> Self {
+        self.read_only_status = Some(status);
+        self
+    }
+}
+
+impl<Pool> SqliteHealthIndicator<Pool>
+where
+    Pool: TryPingable + SelectText + Send + Sync + 'static,
+{
+    async fn select(&self, expression: &str) -> Result<String, PingError> {
+        self.database.pool.select_text(expression).await
+    }
+
+    async fn check(&self, detail: &mut HealthDetail) -> Result<(), PingError> {
+        let journal_mode = self
+            .select("(SELECT journal_mode FROM pragma_journal_mode())")
+            .await?;
+        detail.with_detail("journal_mode".to_owned(), journal_mode);
+
+        if self.quick_check {
+            let result = self
+                .select("(SELECT group_concat(quick_check, char(10)) FROM pragma_quick_check())")
+                .await?;
+            if result != "ok" {
+                detail.status = HealthStatus::Down;
+            }
+            detail.with_detail("quick_check".to_owned(), result);
+        }
+
+        let path = self
+            .select("(SELECT file FROM pragma_database_list WHERE name = 'main')")
+            .await?;
+        if path.is_empty() {
+            return Ok(());
+        }
+
+        let path = PathBuf::from(path);
+        let file = {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || FileStatus::read(&path)).await??
+        };
+
+        detail.with_detail("path".to_owned(), path.to_string_lossy());
+        detail.with_detail("size_bytes".to_owned(), file.size);
+        if let Some(wal_size) = file.wal_size {
+            detail.with_detail("wal_size_bytes".to_owned(), wal_size);
+        }
+        detail.with_detail("read_only".to_owned(), file.read_only);
+
+        if let (HealthStatus::Up, true, Some(status)) =
+            (&detail.status, file.read_only, &self.read_only_status)
+        {
+            detail.status = status.clone();
+        }
+
+        Ok(())
+    }
+}
+
+impl<Pool> From<DatabaseHealthIndicator<Pool>> for SqliteHealthIndicator<Pool>
+where
+    Pool: TryPingable + SelectText,
+{
+    fn from(database: DatabaseHealthIndicator<Pool>) -> Self {
+        SqliteHealthIndicator {
+            database,
+            quick_check: false,
+            read_only_status: None,
+        }
+    }
+}
+
+#[async_trait]
+impl<Pool> HealthIndicator for SqliteHealthIndicator<Pool>
+where
+    Pool: TryPingable + SelectText + Send + Sync + 'static,
+{
+    fn name(&self) -> String {
+        self.database.name()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        let mut detail = self.database.details().await;
+        if detail.status == HealthStatus::Down {
+            return detail;
+        }
+
+        if let Err(error) = self.check(&mut detail).await {
+            detail.status = HealthStatus::Down;
+            detail.with_detail("error".to_owned(), error.to_string());
+        }
+        detail
+    }
+}
+
+struct FileStatus {
+    size: u64,
+    wal_size: Option<u64>,
+    read_only: bool,
+}
+
+impl FileStatus {
+    fn read(path: &Path) -> io::Result<FileStatus> {
+        let size = std::fs::metadata(path)?.len();
+
+        let mut wal = path.as_os_str().to_owned();
+        wal.push("-wal");
+        let wal_size = std::fs::metadata(wal).ok().map(|metadata| metadata.len());
+
+        let read_only = is_read_only(path)?;
+
+        Ok(FileStatus {
+            size,
+            wal_size,
+            read_only,
+        })
+    }
+}
+
+/// Whether SQLite can not write to the file, checked without opening it: closing any descriptor of
+/// the database file would release the locks SQLite holds on it.
+#[cfg(unix)]
+fn is_read_only(path: &Path) -> io::Result<bool> {
+    use rustix::fs::{Access, StatVfsMountFlags};
+    use rustix::io::Errno;
+
+    if rustix::fs::statvfs(path)?
+        .f_flag
+        .contains(StatVfsMountFlags::RDONLY)
+    {
+        return Ok(true);
+    }
+    match rustix::fs::access(path, Access::WRITE_OK) {
+        Ok(()) => Ok(false),
+        Err(Errno::ACCESS | Errno::PERM | Errno::ROFS) => Ok(true),
+        Err(error) => Err(error.into()),
+    }
+}
+
+#[cfg(not(unix))]
+fn is_read_only(path: &Path) -> io::Result<bool> {
+    Ok(std::fs::metadata(path)?.permissions().readonly())
+}
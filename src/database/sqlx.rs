@@ -1,17 +1,315 @@
-use crate::database::Pingable;
+use crate::database::{
+    DatabaseInfo, DatabaseKind, DatabaseMetadata, PingError, PoolState, PoolStatistics,
+    ReplicationLag, SelectText, TryPingable, ValidationQuery,
+};
 use async_trait::async_trait;
 use sqlx::pool::Pool;
-use sqlx::{Connection, Database};
+use sqlx::{ColumnIndex, Connection, Database, Decode, Executor, Row, Type};
+use std::time::Duration;
+
+/// Whether the server is a replica, whether it is streaming from its primary, and the age of the
+/// last replayed transaction unless the replica already replayed everything it received, in
+/// which case it is not behind even if the primary has been idle.
+const POSTGRES_REPLICATION_LAG: &str = "SELECT pg_is_in_recovery(), \
+    EXISTS (SELECT 1 FROM pg_stat_wal_receiver WHERE status = 'streaming'), CAST(CASE \
+    WHEN pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0 \
+    ELSE EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp()) \
+    END AS DOUBLE PRECISION)";
+
+#[async_trait]
+impl<DB> TryPingable for Pool<DB>
+where
+    DB: Database,
+{
+    async fn try_ping(&self) -> Result<(), PingError> {
+        let mut conn = self.acquire().await?;
+        conn.ping().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<DB> ValidationQuery for Pool<DB>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    async fn validate(&self, query: &str) -> Result<Option<u64>, PingError> {
+        let rows = self.fetch_all(query).await?;
+        Ok(Some(rows.len() as u64))
+    }
+}
 
 #[async_trait]
-impl<DB> Pingable for Pool<DB>
+impl<DB> PoolState for Pool<DB>
 where
     DB: Database,
 {
-    async fn ping(&self) -> bool {
-        match self.acquire().await {
-            Ok(mut conn) => conn.ping().await.is_ok(),
-            Err(_) => false,
+    async fn statistics(&self) -> PoolStatistics {
+        let size = self.size();
+        let idle = self.num_idle() as u32;
+        PoolStatistics {
+            size,
+            idle,
+            in_use: size.saturating_sub(idle),
+            max_size: Some(self.options().get_max_connections()),
         }
     }
 }
+
+#[async_trait]
+impl<DB> SelectText for Pool<DB>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'r> String: Decode<'r, DB> + Type<DB>,
+    usize: ColumnIndex<DB::Row>,
+{
+    async fn select_text(&self, expression: &str) -> Result<String, PingError> {
+        let query = format!("SELECT {expression}");
+        Ok(self.fetch_one(query.as_str()).await?.try_get(0)?)
+    }
+}
+
+fn database_kind<DB>() -> DatabaseKind
+where
+    DB: Database,
+{
+    match DB::NAME {
+        "PostgreSQL" => DatabaseKind::Postgres,
+        "MySQL" => DatabaseKind::MySql,
+        "SQLite" => DatabaseKind::Sqlite,
+        name => DatabaseKind::Other(name.to_owned()),
+    }
+}
+
+#[async_trait]
+impl<DB> DatabaseMetadata for Pool<DB>
+where
+    DB: Database,
+    Self: SelectText,
+{
+    async fn database_info(&self) -> Result<DatabaseInfo, PingError> {
+        let kind = database_kind::<DB>();
+
+        let version = match kind.version_expression() {
+            Some(expression) => Some(self.select_text(expression).await?),
+            None => None,
+        };
+
+        Ok(DatabaseInfo { kind, version })
+    }
+}
+
+// MySQL reports the lag of `SHOW REPLICA STATUS` since 8.0.22, older versions and MariaDB before
+// 10.5.1 only know `SHOW SLAVE STATUS`, which MySQL 8.4 removed.
+#[async_trait]
+impl<DB> ReplicationLag for Pool<DB>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'r> bool: Decode<'r, DB> + Type<DB>,
+    for<'r> f64: Decode<'r, DB> + Type<DB>,
+    for<'r> i64: Decode<'r, DB> + Type<DB>,
+    usize: ColumnIndex<DB::Row>,
+    for<'a> &'a str: ColumnIndex<DB::Row>,
+{
+    async fn replication_lag(&self) -> Result<Duration, PingError> {
+        match DB::NAME {
+            "PostgreSQL" => postgres_lag(&self.fetch_one(POSTGRES_REPLICATION_LAG).await?),
+            "MySQL" => {
+                let row = match self.fetch_optional("SHOW REPLICA STATUS").await {
+                    Err(sqlx::Error::Database(error))
+                        if error.code().as_deref() == Some("42000") =>
+                    {
+                        self.fetch_optional("SHOW SLAVE STATUS").await?
+                    }
+                    row => row?,
+                };
+                mysql_lag(&row.ok_or("the server is not a replica")?)
+            }
+            name => Err(format!("replication lag is not supported for {name}").into()),
+        }
+    }
+}
+
+/// Decodes a row of [POSTGRES_REPLICATION_LAG].
+fn postgres_lag<R>(row: &R) -> Result<Duration, PingError>
+where
+    R: Row,
+    for<'r> bool: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> f64: Decode<'r, R::Database> + Type<R::Database>,
+    usize: ColumnIndex<R>,
+{
+    if !row.try_get::<bool, _>(0)? {
+        return Err("the server is not a replica".into());
+    }
+    // The received position stops moving once the WAL receiver disconnects, so a replica that
+    // caught up with it would report no lag while falling behind its primary.
+    if !row.try_get::<bool, _>(1)? {
+        return Err("the replica is not streaming from its primary".into());
+    }
+    match row.try_get::<Option<f64>, _>(2)? {
+        Some(seconds) => Ok(Duration::from_secs_f64(seconds.max(0.0))),
+        None => Err("the replica has not replayed any transaction yet".into()),
+    }
+}
+
+/// Decodes the lag of a replica status row, named after the source or, before MySQL 8.0.22 and
+/// on MariaDB, after the master.
+fn mysql_lag<R>(row: &R) -> Result<Duration, PingError>
+where
+    R: Row,
+    for<'r> i64: Decode<'r, R::Database> + Type<R::Database>,
+    for<'a> &'a str: ColumnIndex<R>,
+{
+    let column = ["Seconds_Behind_Source", "Seconds_Behind_Master"]
+        .into_iter()
+        .find(|column| row.try_column(*column).is_ok())
+        .ok_or("the replica status does not report the lag")?;
+    // The column is unsigned, which the checked getters refuse to decode as i64.
+    match row.try_get_unchecked::<Option<i64>, _>(column)? {
+        Some(seconds) => Ok(Duration::from_secs(seconds.max(0) as u64)),
+        None => Err("replication is not running".into()),
+    }
+}
+
+#[cfg(feature = "sqlx-migrate")]
+mod migrate {
+    use crate::database::migration::table_exists;
+    use crate::database::{PendingMigrations, PingError, SelectText};
+    use async_trait::async_trait;
+    use sqlx::migrate::{Migrate, Migrator};
+    use sqlx::pool::Pool;
+    use sqlx::Database;
+    use std::collections::HashSet;
+
+    /// The migrations of a [Migrator], for example `sqlx::migrate!()`, checked against a pool.
+    pub struct SqlxMigrations<DB>
+    where
+        DB: Database,
+    {
+        migrator: Migrator,
+        pool: Pool<DB>,
+    }
+
+    impl<DB> SqlxMigrations<DB>
+    where
+        DB: Database,
+    {
+        pub fn new(migrator: Migrator, pool: Pool<DB>) -> Self {
+            SqlxMigrations { migrator, pool }
+        }
+    }
+
+    #[async_trait]
+    impl<DB> PendingMigrations for SqlxMigrations<DB>
+    where
+        DB: Database,
+        DB::Connection: Migrate,
+        Pool<DB>: SelectText,
+    {
+        async fn pending_migrations(&self) -> Result<Vec<String>, PingError> {
+            let kind = super::database_kind::<DB>();
+            let mut applied = HashSet::new();
+            if table_exists(&self.pool, &kind, "_sqlx_migrations").await? {
+                let mut conn = self.pool.acquire().await?;
+                applied.extend(
+                    conn.list_applied_migrations()
+                        .await?
+                        .into_iter()
+                        .map(|migration| migration.version),
+                );
+            }
+
+            Ok(self
+                .migrator
+                .iter()
+                .filter(|migration| !migration.migration_type.is_down_migration())
+                .filter(|migration| !applied.contains(&migration.version))
+                .map(|migration| format!("{}/{}", migration.version, migration.description))
+                .collect())
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-migrate")]
+pub use migrate::SqlxMigrations;
+
+#[cfg(test)]
+mod test {
+    use crate::database::sqlx::{mysql_lag, postgres_lag};
+    use crate::database::PingError;
+    use sqlx::sqlite::{SqlitePool, SqliteRow};
+    use std::time::Duration;
+
+    /// Decodes the row selected by `query`, the decoding only depends on the shape of the rows,
+    /// which SQLite can produce.
+    async fn decode(
+        query: &str,
+        decode: fn(&SqliteRow) -> Result<Duration, PingError>,
+    ) -> Result<Duration, PingError> {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        decode(&sqlx::query(query).fetch_one(&pool).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_postgres_lag() {
+        let lag = |query| decode(query, postgres_lag);
+
+        assert_eq!(
+            lag("SELECT 1, 1, CAST(2.5 AS REAL)").await.unwrap(),
+            Duration::from_millis(2500)
+        );
+        // The clocks of the primary and the replica may disagree.
+        assert_eq!(
+            lag("SELECT 1, 1, CAST(-1 AS REAL)").await.unwrap(),
+            Duration::ZERO
+        );
+        assert_eq!(
+            lag("SELECT 0, 0, NULL").await.unwrap_err().to_string(),
+            "the server is not a replica"
+        );
+        assert_eq!(
+            lag("SELECT 1, 0, CAST(0 AS REAL)")
+                .await
+                .unwrap_err()
+                .to_string(),
+            "the replica is not streaming from its primary"
+        );
+        assert_eq!(
+            lag("SELECT 1, 1, NULL").await.unwrap_err().to_string(),
+            "the replica has not replayed any transaction yet"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mysql_lag() {
+        let lag = |query| decode(query, mysql_lag);
+
+        assert_eq!(
+            lag("SELECT 'Yes' AS Replica_IO_Running, 42 AS Seconds_Behind_Source")
+                .await
+                .unwrap(),
+            Duration::from_secs(42)
+        );
+        assert_eq!(
+            lag("SELECT 7 AS Seconds_Behind_Master").await.unwrap(),
+            Duration::from_secs(7)
+        );
+        assert_eq!(
+            lag("SELECT NULL AS Seconds_Behind_Source")
+                .await
+                .unwrap_err()
+                .to_string(),
+            "replication is not running"
+        );
+        assert_eq!(
+            lag("SELECT 'No' AS Replica_IO_Running")
+                .await
+                .unwrap_err()
+                .to_string(),
+            "the replica status does not report the lag"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use crate::aggregator::StatusAggregator;
+use crate::mapper::HttpCodeStatusMapper;
+use crate::{HealthDetail, HealthDetails};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// A named subset of the indicators of a [crate::Health], served by [crate::health_group].
+///
+/// A group includes every indicator unless indicators are explicitly included, excluded
+/// indicators are always left out. The status aggregator and HTTP mapper default to the ones
+/// configured on the [crate::HealthBuilder].
+///
+/// ```
+/// use axum_health::{Health, HealthGroup};
+///
+/// let health = Health::builder()
+///     .with_group(HealthGroup::new("liveness".to_owned()).include("ping".to_owned()))
+///     .with_group(HealthGroup::new("readiness".to_owned()).exclude("ping".to_owned()))
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct HealthGroup {
+    pub(crate) name: String,
+    include: BTreeSet<String>,
+    exclude: BTreeSet<String>,
+    pub(crate) status_aggregator: Option<Arc<dyn StatusAggregator + Send + Sync + 'static>>,
+    pub(crate) http_mapper: Option<Arc<dyn HttpCodeStatusMapper + Send + Sync + 'static>>,
+    show_components: bool,
+    show_details: bool,
+}
+
+impl HealthGroup {
+    pub fn new(name: String) -> Self {
+        HealthGroup {
+            name,
+            include: Default::default(),
+            exclude: Default::default(),
+            status_aggregator: None,
+            http_mapper: None,
+            show_components: true,
+            show_details: true,
+        }
+    }
+
+    pub fn include(mut self, indicator: String) -> Self {
+        self.include.insert(indicator);
+        self
+    }
+
+    pub fn exclude(mut self, indicator: String) -> Self {
+        self.exclude.insert(indicator);
+        self
+    }
+
+    pub fn with_status_aggregator<A>(mut self, aggregator: A) -> Self
+    where
+        A: StatusAggregator + Send + Sync + 'static,
+    {
+        self.status_aggregator = Some(Arc::new(aggregator));
+        self
+    }
+
+    pub fn with_http_mapper<M>(mut self, mapper: M) -> Self
+    where
+        M: HttpCodeStatusMapper + Send + Sync + 'static,
+    {
+        self.http_mapper = Some(Arc::new(mapper));
+        self
+    }
+
+    /// Whether the response lists the components of the group, defaults to `true`.
+    pub fn show_components(mut self, show: bool) -> Self {
+        self.show_components = show;
+        self
+    }
+
+    /// Whether the listed components include their details, defaults to `true`.
+    pub fn show_details(mut self, show: bool) -> Self {
+        self.show_details = show;
+        self
+    }
+
+    pub(crate) fn contains(&self, indicator: &str) -> bool {
+        (self.include.is_empty() || self.include.contains(indicator))
+            && !self.exclude.contains(indicator)
+    }
+
+    pub(crate) fn apply_visibility(&self, mut details: HealthDetails) -> HealthDetails {
+        if !self.show_components {
+            details.components.clear();
+        } else if !self.show_details {
+            details.components.values_mut().for_each(hide_details);
+        }
+        details
+    }
+}
+
+fn hide_details(detail: &mut HealthDetail) {
+    detail.details.clear();
+    detail.components.values_mut().for_each(hide_details);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::group::HealthGroup;
+
+    #[test]
+    fn test_contains() {
+        let all = HealthGroup::new("all".to_owned());
+        assert!(all.contains("db"));
+
+        let included = HealthGroup::new("included".to_owned()).include("db".to_owned());
+        assert!(included.contains("db"));
+        assert!(!included.contains("redis"));
+
+        let excluded = HealthGroup::new("excluded".to_owned()).exclude("db".to_owned());
+        assert!(!excluded.contains("db"));
+        assert!(excluded.contains("redis"));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{HealthDetail, HealthDetails, HealthIndicator};
+use async_trait::async_trait;
+use reqwest::{Client, Method, Response, StatusCode};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+/// The largest downstream health response read, in bytes.
+const MAX_BODY_LEN: usize = 1024 * 1024;
+
+/// Checks a downstream HTTP service by requesting `url`, reporting the `url`, the `http_status`
+/// of the response and the `latency_ms` of the request.
+///
+/// The client is built without TLS support, enable one of the TLS features of `reqwest` or pass
+/// a configured client to [HttpHealthIndicator::with_client] to check `https` URLs.
+pub struct HttpHealthIndicator {
+    name: String,
+    url: String,
+    client: Client,
+    timeout: Duration,
+    method: Method,
+    up_statuses: BTreeSet<StatusCode>,
+    downstream_health: bool,
+}
+
+impl HttpHealthIndicator {
+    pub fn new(name: String, url: String) -> Self {
+        HttpHealthIndicator {
+            name,
+            url,
+            client: Client::new(),
+            timeout: Duration::from_secs(5),
+            method: Method::GET,
+            up_statuses: BTreeSet::new(),
+            downstream_health: false,
+        }
+    }
+
+    /// Sends the requests with `client`, for example to set default headers.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Fails the check when the request, including reading the response, takes longer than
+    /// `timeout`, defaults to 5 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sends `method` requests instead of `GET`, for example `HEAD`.
+    pub fn with_method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Reports [HealthStatus::Up](crate::HealthStatus::Up) for responses with `status`. Once any
+    /// status is added, only the added ones are up, otherwise every `2xx` response is.
+    pub fn with_up_status(mut self, status: StatusCode) -> Self {
+        self.up_statuses.insert(status);
+        self
+    }
+
+    /// Reads the response as the [HealthDetails] of a downstream axum-health service, reporting
+    /// its status and components instead of deriving the status from the response code.
+    /// Responses larger than 1 MiB are reported as [HealthStatus::Down](crate::HealthStatus::Down).
+    pub fn with_downstream_health(mut self) -> Self {
+        self.downstream_health = true;
+        self
+    }
+
+    fn is_up(&self, status: StatusCode) -> bool {
+        if self.up_statuses.is_empty() {
+            status.is_success()
+        } else {
+            self.up_statuses.contains(&status)
+        }
+    }
+}
+
+#[async_trait]
+impl HealthIndicator for HttpHealthIndicator {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        let start = tokio::time::Instant::now();
+        let result = match self
+            .client
+            .request(self.method.clone(), &self.url)
+            .timeout(self.timeout)
+            .send()
+            .await
+        {
+            Ok(response) if self.downstream_health => {
+                let status = response.status();
+                read_body(response).await.map(|body| (status, Some(body)))
+            }
+            Ok(response) => Ok((response.status(), None)),
+            Err(error) => Err(error.to_string()),
+        };
+        let latency = start.elapsed();
+
+        let mut detail = match result {
+            Ok((status, None)) => {
+                let mut detail = if self.is_up(status) {
+                    HealthDetail::up()
+                } else {
+                    HealthDetail::down()
+                };
+                detail.with_detail("http_status".to_owned(), status.as_u16());
+                detail
+            }
+            Ok((status, Some(body))) => {
+                let mut detail = match serde_json::from_slice::<HealthDetails>(&body) {
+                    Ok(downstream) => {
+                        let mut detail = HealthDetail::new(downstream.status);
+                        detail.components = downstream.components;
+                        detail
+                    }
+                    Err(error) => {
+                        let mut detail = HealthDetail::down();
+                        detail.with_detail(
+                            "error".to_owned(),
+                            format!("invalid health response: {error}"),
+                        );
+                        detail
+                    }
+                };
+                detail.with_detail("http_status".to_owned(), status.as_u16());
+                detail
+            }
+            Err(error) => {
+                let mut detail = HealthDetail::down();
+                detail.with_detail("error".to_owned(), error);
+                detail
+            }
+        };
+
+        detail.with_detail("url".to_owned(), self.url.as_str());
+        detail.with_detail("latency_ms".to_owned(), latency.as_millis() as u64);
+        detail
+    }
+}
+
+/// Reads the body of `response`, failing once it grows past [MAX_BODY_LEN].
+async fn read_body(mut response: Response) -> Result<Vec<u8>, String> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|error| error.to_string())? {
+        if body.len() + chunk.len() > MAX_BODY_LEN {
+            return Err(format!(
+                "the health response is larger than {MAX_BODY_LEN} bytes"
+            ));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
@@ -1,11 +1,61 @@
+pub mod aggregator;
+mod cache;
+pub mod composite;
+pub mod group;
+pub mod mapper;
 pub mod service;
+mod threshold;
 
 pub mod database;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "socket")]
+pub mod socket;
+pub mod system;
 
+pub use crate::aggregator::*;
+pub use crate::composite::*;
+pub use crate::group::*;
+pub use crate::mapper::*;
 pub use crate::service::*;
 
 pub async fn health(
     axum::Extension(health): axum::Extension<Health>,
 ) -> impl axum::response::IntoResponse {
-    health.details().await
+    let details = health.details().await;
+    (health.status_code(&details.status), axum::Json(details))
+}
+
+/// Serves the [HealthGroup] named by the last path segment, for example `/health/{group}`.
+/// Unknown groups respond with `404 Not Found`.
+pub async fn health_group(
+    axum::Extension(health): axum::Extension<Health>,
+    axum::extract::Path(group): axum::extract::Path<String>,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    match health.group_details(&group).await {
+        Some(details) => (
+            health.group_status_code(&group, &details.status),
+            axum::Json(details),
+        )
+            .into_response(),
+        None => axum::http::StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Serves the single indicator named by the last path segment, for example
+/// `/health/{component}`. Unknown indicators respond with `404 Not Found`.
+///
+/// To serve groups and indicators under the same path use [Health::router].
+pub async fn health_component(
+    axum::Extension(health): axum::Extension<Health>,
+    axum::extract::Path(component): axum::extract::Path<String>,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    match health.component_details(&component).await {
+        Some(detail) => (health.status_code(&detail.status), axum::Json(detail)).into_response(),
+        None => axum::http::StatusCode::NOT_FOUND.into_response(),
+    }
 }
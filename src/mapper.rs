@@ -0,0 +1,108 @@
+use crate::HealthStatus;
+use axum::http::StatusCode;
+use std::collections::HashMap;
+
+/// Maps the aggregated [HealthStatus] of a health response to its HTTP status code.
+pub trait HttpCodeStatusMapper {
+    fn status_code(&self, status: &HealthStatus) -> StatusCode;
+}
+
+/// Looks the status up in a table of mappings, like Spring's `SimpleHttpCodeStatusMapper`.
+///
+/// By default `DOWN` and `OUT_OF_SERVICE` map to `503 Service Unavailable`, anything else maps
+/// to `200 OK`.
+///
+/// ```
+/// use axum::http::StatusCode;
+/// use axum_health::{HealthStatus, SimpleHttpCodeStatusMapper};
+///
+/// let mapper = SimpleHttpCodeStatusMapper::default()
+///     .with_mapping(HealthStatus::Custom("DEGRADED".to_owned()), StatusCode::TOO_MANY_REQUESTS)
+///     .with_mapping(HealthStatus::Unknown, StatusCode::INTERNAL_SERVER_ERROR);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SimpleHttpCodeStatusMapper {
+    mappings: HashMap<HealthStatus, StatusCode>,
+    default: StatusCode,
+}
+
+impl SimpleHttpCodeStatusMapper {
+    /// Creates a mapper with only the given mappings, unmapped statuses respond with `200 OK`.
+    pub fn new<M>(mappings: M) -> Self
+    where
+        M: IntoIterator<Item = (HealthStatus, StatusCode)>,
+    {
+        SimpleHttpCodeStatusMapper {
+            mappings: mappings.into_iter().collect(),
+            default: StatusCode::OK,
+        }
+    }
+
+    pub fn with_mapping(mut self, status: HealthStatus, status_code: StatusCode) -> Self {
+        self.mappings.insert(status, status_code);
+        self
+    }
+
+    /// The status code used for statuses without a mapping.
+    pub fn with_default(mut self, status_code: StatusCode) -> Self {
+        self.default = status_code;
+        self
+    }
+}
+
+impl Default for SimpleHttpCodeStatusMapper {
+    fn default() -> Self {
+        SimpleHttpCodeStatusMapper::new([
+            (HealthStatus::Down, StatusCode::SERVICE_UNAVAILABLE),
+            (HealthStatus::OutOfService, StatusCode::SERVICE_UNAVAILABLE),
+        ])
+    }
+}
+
+impl HttpCodeStatusMapper for SimpleHttpCodeStatusMapper {
+    fn status_code(&self, status: &HealthStatus) -> StatusCode {
+        self.mappings.get(status).copied().unwrap_or(self.default)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mapper::{HttpCodeStatusMapper, SimpleHttpCodeStatusMapper};
+    use crate::HealthStatus;
+    use axum::http::StatusCode;
+
+    #[test]
+    fn test_default_mappings() {
+        let mapper = SimpleHttpCodeStatusMapper::default();
+
+        assert_eq!(mapper.status_code(&HealthStatus::Up), StatusCode::OK);
+        assert_eq!(mapper.status_code(&HealthStatus::Unknown), StatusCode::OK);
+        assert_eq!(
+            mapper.status_code(&HealthStatus::Down),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            mapper.status_code(&HealthStatus::OutOfService),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+
+    #[test]
+    fn test_custom_mappings() {
+        let degraded = HealthStatus::Custom("DEGRADED".to_owned());
+        let mapper =
+            SimpleHttpCodeStatusMapper::new([(HealthStatus::Down, StatusCode::IM_A_TEAPOT)])
+                .with_mapping(degraded.clone(), StatusCode::TOO_MANY_REQUESTS)
+                .with_default(StatusCode::NO_CONTENT);
+
+        assert_eq!(
+            mapper.status_code(&HealthStatus::Down),
+            StatusCode::IM_A_TEAPOT
+        );
+        assert_eq!(mapper.status_code(&degraded), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            mapper.status_code(&HealthStatus::OutOfService),
+            StatusCode::NO_CONTENT
+        );
+    }
+}
@@ -1,16 +1,40 @@
+use crate::aggregator::{SimpleStatusAggregator, StatusAggregator};
+use crate::cache::IndicatorCache;
+use crate::group::HealthGroup;
+use crate::mapper::{HttpCodeStatusMapper, SimpleHttpCodeStatusMapper};
 use async_trait::async_trait;
+use axum::extract::Path;
 use axum::http::StatusCode;
 use axum::middleware::AddExtension;
 use axum::response::{IntoResponse, Response};
-use axum::{Extension, Json};
+use axum::routing::get;
+use axum::{Extension, Json, Router};
 use futures::StreamExt;
-use serde::{Deserialize, Serialize};
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::fmt;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
 use tower_layer::Layer;
 
+pub(crate) type Indicators = BTreeMap<String, Arc<dyn HealthIndicator + Send + Sync + 'static>>;
+
 #[derive(Clone)]
-pub struct Health(Arc<BTreeMap<String, Arc<dyn HealthIndicator + Send + Sync + 'static>>>);
+pub struct Health(Arc<HealthInner>);
+
+struct HealthInner {
+    indicators: Indicators,
+    concurrency_limit: Option<usize>,
+    timeout: Option<Duration>,
+    indicator_timeouts: BTreeMap<String, Duration>,
+    timeout_status: HealthStatus,
+    status_aggregator: Arc<dyn StatusAggregator + Send + Sync + 'static>,
+    http_mapper: Arc<dyn HttpCodeStatusMapper + Send + Sync + 'static>,
+    groups: BTreeMap<String, HealthGroup>,
+    caches: BTreeMap<String, IndicatorCache>,
+}
 
 impl Health {
     pub fn builder() -> HealthBuilder {
@@ -18,22 +42,202 @@ impl Health {
     }
 
     pub async fn details(&self) -> HealthDetails {
-        let statuses = futures::stream::iter(self.0.values())
-            .then(|indicator| async move { (indicator.name(), indicator.details().await) })
-            .collect::<BTreeMap<_, _>>()
+        let components = self.components(|_| true).await;
+        let status = aggregate(self.0.status_aggregator.as_ref(), &components);
+
+        HealthDetails { status, components }
+    }
+
+    /// Evaluates only the indicators of the given group, [None] if there is no such group.
+    pub async fn group_details(&self, group: &str) -> Option<HealthDetails> {
+        let group = self.0.groups.get(group)?;
+
+        let components = self.components(|name| group.contains(name)).await;
+        let aggregator = group
+            .status_aggregator
+            .as_ref()
+            .unwrap_or(&self.0.status_aggregator);
+        let status = aggregate(aggregator.as_ref(), &components);
+
+        Some(group.apply_visibility(HealthDetails { status, components }))
+    }
+
+    /// Evaluates only the indicator with the given name, [None] if there is no such indicator.
+    pub async fn component_details(&self, component: &str) -> Option<HealthDetail> {
+        if !self.0.indicators.contains_key(component) {
+            return None;
+        }
+
+        Some(self.check(component).await)
+    }
+
+    /// Evaluates every cached indicator, regardless of the age of its cached detail. Indicators
+    /// that are already being evaluated are skipped, requests are served the previous snapshot
+    /// until the evaluation finishes.
+    pub async fn refresh(&self) {
+        let limit = self.limit(self.0.caches.len());
+        let refreshes = self
+            .0
+            .caches
+            .keys()
+            .map(|name| {
+                let (health, name) = (self.clone(), name.clone());
+                async move {
+                    let cache = &health.0.caches[&name];
+                    cache.refresh(health.evaluate(&name)).await
+                }
+            })
+            .collect::<Vec<_>>();
+
+        futures::stream::iter(refreshes)
+            .buffer_unordered(limit)
+            .collect::<Vec<_>>()
             .await;
+    }
+
+    /// Spawns a task on the current tokio runtime that calls [Health::refresh] every `interval`,
+    /// so requests are served from the last snapshot instead of evaluating the indicators.
+    /// Only indicators with a cache TTL are refreshed, the TTL should be longer than the interval.
+    ///
+    /// The task stops once every clone of this [Health] has been dropped.
+    pub fn spawn_refresh(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let health = Arc::downgrade(&self.0);
+        tokio::spawn(refresh_periodically(health, interval))
+    }
+
+    /// Creates a router serving `/health` and `/health/{name}`, where `name` is either a
+    /// [HealthGroup] or, if there is no group with that name, a single indicator.
+    pub fn router<S>(&self) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        Router::new()
+            .route("/health", get(crate::health))
+            .route("/health/{name}", get(group_or_component))
+            .layer(self.clone())
+    }
+
+    async fn components<F>(&self, filter: F) -> BTreeMap<String, HealthDetail>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let names = self
+            .0
+            .indicators
+            .keys()
+            .filter(|name| filter(name))
+            .cloned()
+            .collect::<Vec<_>>();
 
-        // If we have no health indicators, we are up, otherwise we take our worst one and respond with that.
-        let worst_status = statuses
+        // All indicators are evaluated concurrently, optionally capped by the concurrency limit.
+        // Collecting into a BTreeMap keeps the component order deterministic regardless of which
+        // indicator finishes first.
+        let limit = self.limit(names.len());
+        let evaluations = names
+            .into_iter()
+            .map(|name| {
+                let health = self.clone();
+                async move {
+                    let detail = health.check(&name).await;
+                    (name, detail)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        futures::stream::iter(evaluations)
+            .buffer_unordered(limit)
+            .collect::<BTreeMap<_, _>>()
+            .await
+    }
+
+    /// Evaluates the indicator, or serves it from its cache if it has one.
+    async fn check(&self, name: &str) -> HealthDetail {
+        match self.0.caches.get(name) {
+            Some(cache) => cache.get(self.evaluate(name)).await,
+            None => self.evaluate(name).await,
+        }
+    }
+
+    /// Evaluates the indicator within its timeout, adding how long it took as `duration_ms`.
+    async fn evaluate(&self, name: &str) -> HealthDetail {
+        let indicator = &self.0.indicators[name];
+
+        let start = tokio::time::Instant::now();
+        let mut detail = match self.timeout(name) {
+            Some(timeout) => match tokio::time::timeout(timeout, indicator.details()).await {
+                Ok(detail) => detail,
+                Err(_) => {
+                    let mut detail = HealthDetail::new(self.0.timeout_status.clone());
+                    detail.with_detail("reason".to_owned(), "timed out".to_owned());
+                    detail
+                }
+            },
+            None => indicator.details().await,
+        };
+
+        detail.with_detail("duration_ms".to_owned(), start.elapsed().as_millis() as u64);
+        detail
+    }
+
+    fn limit(&self, evaluations: usize) -> usize {
+        self.0.concurrency_limit.unwrap_or(evaluations).max(1)
+    }
+
+    /// The HTTP status code to respond with for the given aggregated status.
+    pub fn status_code(&self, status: &HealthStatus) -> StatusCode {
+        self.0.http_mapper.status_code(status)
+    }
+
+    /// The HTTP status code to respond with for the given aggregated status of a group, using the
+    /// mapper of the group if it has one.
+    pub fn group_status_code(&self, group: &str, status: &HealthStatus) -> StatusCode {
+        self.0
+            .groups
+            .get(group)
+            .and_then(|group| group.http_mapper.as_ref())
+            .unwrap_or(&self.0.http_mapper)
+            .status_code(status)
+    }
+
+    fn timeout(&self, name: &str) -> Option<Duration> {
+        self.0
+            .indicator_timeouts
+            .get(name)
+            .copied()
+            .or(self.0.timeout)
+    }
+}
+
+async fn group_or_component(
+    Extension(health): Extension<Health>,
+    Path(name): Path<String>,
+) -> Response {
+    if health.0.groups.contains_key(&name) {
+        crate::health_group(Extension(health), Path(name)).await
+    } else {
+        crate::health_component(Extension(health), Path(name)).await
+    }
+}
+
+pub(crate) fn aggregate(
+    aggregator: &(dyn StatusAggregator + Send + Sync),
+    components: &BTreeMap<String, HealthDetail>,
+) -> HealthStatus {
+    aggregator.aggregate_status(
+        &components
             .values()
             .map(|detail| &detail.status)
-            .max()
-            .cloned()
-            .unwrap_or(HealthStatus::Up);
+            .collect::<Vec<_>>(),
+    )
+}
 
-        HealthDetails {
-            status: worst_status,
-            components: statuses,
+async fn refresh_periodically(health: Weak<HealthInner>, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        match health.upgrade() {
+            Some(health) => Health(health).refresh().await,
+            None => break,
         }
     }
 }
@@ -46,20 +250,135 @@ impl<S> Layer<S> for Health {
     }
 }
 
-#[derive(Default)]
-pub struct HealthBuilder(BTreeMap<String, Arc<dyn HealthIndicator + Send + Sync + 'static>>);
+pub struct HealthBuilder {
+    indicators: Indicators,
+    concurrency_limit: Option<usize>,
+    timeout: Option<Duration>,
+    indicator_timeouts: BTreeMap<String, Duration>,
+    timeout_status: HealthStatus,
+    status_aggregator: Arc<dyn StatusAggregator + Send + Sync + 'static>,
+    http_mapper: Arc<dyn HttpCodeStatusMapper + Send + Sync + 'static>,
+    groups: BTreeMap<String, HealthGroup>,
+    cache_ttl: Option<Duration>,
+    indicator_cache_ttls: BTreeMap<String, Duration>,
+}
+
+impl Default for HealthBuilder {
+    fn default() -> Self {
+        HealthBuilder {
+            indicators: Default::default(),
+            concurrency_limit: None,
+            timeout: None,
+            indicator_timeouts: Default::default(),
+            timeout_status: HealthStatus::Down,
+            status_aggregator: Arc::new(SimpleStatusAggregator::default()),
+            http_mapper: Arc::new(SimpleHttpCodeStatusMapper::default()),
+            groups: Default::default(),
+            cache_ttl: None,
+            indicator_cache_ttls: Default::default(),
+        }
+    }
+}
 
 impl HealthBuilder {
     pub fn with_indicator<I>(mut self, indicator: I) -> Self
     where
         I: HealthIndicator + Send + Sync + 'static,
     {
-        self.0.insert(indicator.name(), Arc::new(indicator));
+        self.indicators
+            .insert(indicator.name(), Arc::new(indicator));
+        self
+    }
+
+    /// Limits how many indicators are evaluated at the same time, by default all of them are.
+    pub fn with_concurrency_limit(mut self, limit: usize) -> Self {
+        self.concurrency_limit = Some(limit);
+        self
+    }
+
+    /// Sets a deadline for every indicator, indicators that do not respond in time are reported
+    /// with the timeout status.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Overrides the global timeout for the indicator with the given name.
+    pub fn with_indicator_timeout(mut self, name: String, timeout: Duration) -> Self {
+        self.indicator_timeouts.insert(name, timeout);
+        self
+    }
+
+    /// The status reported for indicators that time out, defaults to [HealthStatus::Down].
+    pub fn with_timeout_status(mut self, status: HealthStatus) -> Self {
+        self.timeout_status = status;
+        self
+    }
+
+    /// Sets how the statuses of the indicators are combined, defaults to [SimpleStatusAggregator].
+    pub fn with_status_aggregator<A>(mut self, aggregator: A) -> Self
+    where
+        A: StatusAggregator + Send + Sync + 'static,
+    {
+        self.status_aggregator = Arc::new(aggregator);
+        self
+    }
+
+    /// Sets how the aggregated status is mapped to the HTTP status code of the health response,
+    /// defaults to [SimpleHttpCodeStatusMapper].
+    pub fn with_http_mapper<M>(mut self, mapper: M) -> Self
+    where
+        M: HttpCodeStatusMapper + Send + Sync + 'static,
+    {
+        self.http_mapper = Arc::new(mapper);
+        self
+    }
+
+    /// Adds a [HealthGroup], replacing any group with the same name.
+    pub fn with_group(mut self, group: HealthGroup) -> Self {
+        self.groups.insert(group.name.clone(), group);
+        self
+    }
+
+    /// Caches the details of every indicator for the given time to live, cached details include
+    /// a `checked_at` timestamp. See [Health::spawn_refresh] to refresh them in the background.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Overrides the global cache TTL for the indicator with the given name, a zero TTL disables
+    /// caching for it.
+    pub fn with_indicator_cache_ttl(mut self, name: String, ttl: Duration) -> Self {
+        self.indicator_cache_ttls.insert(name, ttl);
         self
     }
 
     pub fn build(self) -> Health {
-        Health(Arc::new(self.0))
+        let caches = self
+            .indicators
+            .keys()
+            .filter_map(|name| {
+                let ttl = self
+                    .indicator_cache_ttls
+                    .get(name)
+                    .or(self.cache_ttl.as_ref());
+                ttl.filter(|ttl| !ttl.is_zero())
+                    .map(|ttl| (name.clone(), IndicatorCache::new(*ttl)))
+            })
+            .collect();
+
+        Health(Arc::new(HealthInner {
+            indicators: self.indicators,
+            concurrency_limit: self.concurrency_limit,
+            timeout: self.timeout,
+            indicator_timeouts: self.indicator_timeouts,
+            timeout_status: self.timeout_status,
+            status_aggregator: self.status_aggregator,
+            http_mapper: self.http_mapper,
+            groups: self.groups,
+            caches,
+        }))
     }
 }
 
@@ -69,7 +388,12 @@ pub trait HealthIndicator {
     async fn details(&self) -> HealthDetail;
 }
 
-#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+/// Serializes to the Spring Boot Actuator status codes, `UP`, `DOWN`, `OUT_OF_SERVICE`, `UNKNOWN`
+/// and the bare code of [HealthStatus::Custom].
+///
+/// Deserialization also accepts the variant names (`Up`, `OutOfService`, `{"Custom": "X"}`)
+/// produced by earlier versions of this crate.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum HealthStatus {
     Up,
     Down,
@@ -78,6 +402,88 @@ pub enum HealthStatus {
     Custom(String),
 }
 
+impl HealthStatus {
+    /// The `DEGRADED` [HealthStatus::Custom] status, reported by the system indicators when a
+    /// resource nears its limit.
+    pub fn degraded() -> Self {
+        HealthStatus::Custom("DEGRADED".to_owned())
+    }
+
+    pub fn code(&self) -> &str {
+        match self {
+            HealthStatus::Up => "UP",
+            HealthStatus::Down => "DOWN",
+            HealthStatus::OutOfService => "OUT_OF_SERVICE",
+            HealthStatus::Unknown => "UNKNOWN",
+            HealthStatus::Custom(code) => code,
+        }
+    }
+
+    fn from_code(code: &str) -> Self {
+        match code {
+            "UP" | "Up" => HealthStatus::Up,
+            "DOWN" | "Down" => HealthStatus::Down,
+            "OUT_OF_SERVICE" | "OutOfService" => HealthStatus::OutOfService,
+            "UNKNOWN" | "Unknown" => HealthStatus::Unknown,
+            code => HealthStatus::Custom(code.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl Serialize for HealthStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for HealthStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct HealthStatusVisitor;
+
+        impl<'de> Visitor<'de> for HealthStatusVisitor {
+            type Value = HealthStatus;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a status code or a {\"Custom\": code} map")
+            }
+
+            fn visit_str<E>(self, code: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(HealthStatus::from_code(code))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                match map.next_entry::<String, String>()? {
+                    Some((variant, code)) if variant == "Custom" => Ok(HealthStatus::Custom(code)),
+                    Some((variant, _)) => {
+                        Err(serde::de::Error::unknown_variant(&variant, &["Custom"]))
+                    }
+                    None => Err(serde::de::Error::invalid_length(0, &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(HealthStatusVisitor)
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct HealthDetails {
     pub status: HealthStatus,
@@ -85,12 +491,11 @@ pub struct HealthDetails {
     pub components: BTreeMap<String, HealthDetail>,
 }
 
+/// Responds using the default [SimpleHttpCodeStatusMapper], use [Health::status_code] to respect
+/// the configured mapper.
 impl IntoResponse for HealthDetails {
     fn into_response(self) -> Response {
-        let status_code = match &self.status {
-            HealthStatus::Down | HealthStatus::OutOfService => StatusCode::SERVICE_UNAVAILABLE,
-            _ => StatusCode::OK,
-        };
+        let status_code = SimpleHttpCodeStatusMapper::default().status_code(&self.status);
         (status_code, Json(self)).into_response()
     }
 }
@@ -98,7 +503,11 @@ impl IntoResponse for HealthDetails {
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct HealthDetail {
     pub status: HealthStatus,
-    pub details: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, Value>,
+    /// The children of a composite indicator, see [crate::CompositeHealthIndicator].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, HealthDetail>,
 }
 
 impl HealthDetail {
@@ -106,6 +515,7 @@ impl HealthDetail {
         HealthDetail {
             status,
             details: Default::default(),
+            components: Default::default(),
         }
     }
 
@@ -117,31 +527,63 @@ impl HealthDetail {
         HealthDetail::new(HealthStatus::Down)
     }
 
-    pub fn with_detail(&mut self, name: String, detail: String) -> &mut Self {
-        self.details.insert(name, detail);
+    /// Adds a detail, anything that converts into a JSON [Value] can be used, such as strings,
+    /// numbers, booleans or a [serde_json::json] object.
+    pub fn with_detail(&mut self, name: String, detail: impl Into<Value>) -> &mut Self {
+        self.details.insert(name, detail.into());
         self
     }
+
+    /// Adds a detail from any serializable value, for example a struct of pool statistics.
+    pub fn with_serialized_detail<T>(
+        &mut self,
+        name: String,
+        detail: &T,
+    ) -> Result<&mut Self, serde_json::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.details.insert(name, serde_json::to_value(detail)?);
+        Ok(self)
+    }
 }
 
 #[cfg(test)]
-mod test {
-    use crate::health;
+pub(crate) mod test {
+    use crate::aggregator::SimpleStatusAggregator;
+    use crate::group::HealthGroup;
+    use crate::mapper::SimpleHttpCodeStatusMapper;
     use crate::service::{Health, HealthDetail, HealthDetails, HealthIndicator, HealthStatus};
+    use crate::{health, health_component, health_group};
     use async_trait::async_trait;
     use axum::http::StatusCode;
     use axum::routing::get;
     use axum::Router;
     use axum_test::TestServer;
+    use serde::Serialize;
     use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
 
     pub struct MockHealthIndicator {
         name: String,
         response: HealthDetail,
+        delay: Option<Duration>,
     }
 
     impl MockHealthIndicator {
         pub fn new(name: String, response: HealthDetail) -> Self {
-            MockHealthIndicator { name, response }
+            MockHealthIndicator {
+                name,
+                response,
+                delay: None,
+            }
+        }
+
+        pub fn with_delay(mut self, delay: Duration) -> Self {
+            self.delay = Some(delay);
+            self
         }
     }
 
@@ -152,10 +594,27 @@ mod test {
         }
 
         async fn details(&self) -> HealthDetail {
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
             self.response.clone()
         }
     }
 
+    fn without_duration(mut detail: HealthDetail) -> HealthDetail {
+        detail.details.remove("duration_ms");
+        detail
+    }
+
+    fn without_durations(mut details: HealthDetails) -> HealthDetails {
+        details.components = details
+            .components
+            .into_iter()
+            .map(|(name, detail)| (name, without_duration(detail)))
+            .collect();
+        details
+    }
+
     #[tokio::test]
     async fn test_health() {
         let router = Router::new()
@@ -165,7 +624,7 @@ mod test {
         let server = TestServer::new(router).unwrap();
         let response = server.get("/health").await;
 
-        let body = response.json::<HealthDetails>();
+        let body = without_durations(response.json::<HealthDetails>());
         let expected = HealthDetails {
             status: HealthStatus::Up,
             components: Default::default(),
@@ -189,7 +648,7 @@ mod test {
 
         assert_eq!(response.status_code(), StatusCode::OK);
 
-        let body = response.json::<HealthDetails>();
+        let body = without_durations(response.json::<HealthDetails>());
 
         let expected = HealthDetails {
             status: HealthStatus::Up,
@@ -219,7 +678,7 @@ mod test {
 
         assert_eq!(response.status_code(), StatusCode::SERVICE_UNAVAILABLE);
 
-        let body = response.json::<HealthDetails>();
+        let body = without_durations(response.json::<HealthDetails>());
 
         let expected = HealthDetails {
             status: HealthStatus::Down,
@@ -231,4 +690,515 @@ mod test {
 
         assert_eq!(body, expected);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_indicators_run_concurrently() {
+        let health = Health::builder()
+            .with_indicator(
+                MockHealthIndicator::new("first".to_string(), HealthDetail::up())
+                    .with_delay(Duration::from_secs(1)),
+            )
+            .with_indicator(
+                MockHealthIndicator::new("second".to_string(), HealthDetail::down())
+                    .with_delay(Duration::from_secs(1)),
+            )
+            .build();
+
+        let start = tokio::time::Instant::now();
+        let details = health.details().await;
+
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+        assert_eq!(details.status, HealthStatus::Down);
+        assert_eq!(
+            details.components.keys().collect::<Vec<_>>(),
+            vec!["first", "second"]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrency_limit() {
+        let health = Health::builder()
+            .with_concurrency_limit(1)
+            .with_indicator(
+                MockHealthIndicator::new("first".to_string(), HealthDetail::up())
+                    .with_delay(Duration::from_secs(1)),
+            )
+            .with_indicator(
+                MockHealthIndicator::new("second".to_string(), HealthDetail::up())
+                    .with_delay(Duration::from_secs(1)),
+            )
+            .build();
+
+        let start = tokio::time::Instant::now();
+        let details = health.details().await;
+
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+        assert_eq!(details.components.len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_timeout() {
+        let health = Health::builder()
+            .with_timeout(Duration::from_secs(1))
+            .with_indicator(MockHealthIndicator::new(
+                "fast".to_string(),
+                HealthDetail::up(),
+            ))
+            .with_indicator(
+                MockHealthIndicator::new("slow".to_string(), HealthDetail::up())
+                    .with_delay(Duration::from_secs(10)),
+            )
+            .build();
+
+        let details = health.details().await;
+        assert_eq!(details.components["slow"].details["duration_ms"], 1000);
+        let details = without_durations(details);
+
+        let mut expected = HealthDetail::down();
+        expected.with_detail("reason".to_owned(), "timed out".to_owned());
+
+        assert_eq!(details.status, HealthStatus::Down);
+        assert_eq!(details.components["fast"], HealthDetail::up());
+        assert_eq!(details.components["slow"], expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_indicator_timeout() {
+        let health = Health::builder()
+            .with_timeout(Duration::from_secs(1))
+            .with_indicator_timeout("slow".to_string(), Duration::from_secs(20))
+            .with_timeout_status(HealthStatus::Unknown)
+            .with_indicator(
+                MockHealthIndicator::new("slow".to_string(), HealthDetail::up())
+                    .with_delay(Duration::from_secs(10)),
+            )
+            .with_indicator(
+                MockHealthIndicator::new("slower".to_string(), HealthDetail::up())
+                    .with_delay(Duration::from_secs(10)),
+            )
+            .build();
+
+        let details = without_durations(health.details().await);
+
+        assert_eq!(details.components["slow"], HealthDetail::up());
+        assert_eq!(details.components["slower"].status, HealthStatus::Unknown);
+    }
+
+    #[test]
+    fn test_status_serialization() {
+        let statuses = [
+            (HealthStatus::Up, "\"UP\""),
+            (HealthStatus::Down, "\"DOWN\""),
+            (HealthStatus::OutOfService, "\"OUT_OF_SERVICE\""),
+            (HealthStatus::Unknown, "\"UNKNOWN\""),
+            (HealthStatus::Custom("DEGRADED".to_owned()), "\"DEGRADED\""),
+        ];
+
+        for (status, json) in statuses {
+            assert_eq!(serde_json::to_string(&status).unwrap(), json);
+            assert_eq!(serde_json::from_str::<HealthStatus>(json).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn test_legacy_status_deserialization() {
+        let statuses = [
+            ("\"Up\"", HealthStatus::Up),
+            ("\"Down\"", HealthStatus::Down),
+            ("\"OutOfService\"", HealthStatus::OutOfService),
+            ("\"Unknown\"", HealthStatus::Unknown),
+            (
+                "{\"Custom\":\"DEGRADED\"}",
+                HealthStatus::Custom("DEGRADED".to_owned()),
+            ),
+        ];
+
+        for (json, status) in statuses {
+            assert_eq!(serde_json::from_str::<HealthStatus>(json).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn test_details_serialization() {
+        let mut down = HealthDetail::down();
+        down.with_detail("error".to_owned(), "connection refused".to_owned());
+
+        let details = HealthDetails {
+            status: HealthStatus::Down,
+            components: BTreeMap::from_iter([
+                ("db".to_owned(), down),
+                ("disk".to_owned(), HealthDetail::up()),
+            ]),
+        };
+
+        let json = serde_json::json!({
+            "status": "DOWN",
+            "components": {
+                "db": {
+                    "status": "DOWN",
+                    "details": {
+                        "error": "connection refused"
+                    }
+                },
+                "disk": {
+                    "status": "UP"
+                }
+            }
+        });
+
+        assert_eq!(serde_json::to_value(&details).unwrap(), json);
+        assert_eq!(
+            serde_json::from_value::<HealthDetails>(json).unwrap(),
+            details
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_aggregator() {
+        let degraded = HealthStatus::Custom("DEGRADED".to_owned());
+        let indicators = || {
+            Health::builder()
+                .with_indicator(MockHealthIndicator::new(
+                    "unknown".to_string(),
+                    HealthDetail::new(HealthStatus::Unknown),
+                ))
+                .with_indicator(MockHealthIndicator::new(
+                    "degraded".to_string(),
+                    HealthDetail::new(degraded.clone()),
+                ))
+        };
+
+        let health = indicators().build();
+        assert_eq!(health.details().await.status, HealthStatus::Unknown);
+
+        let health = indicators()
+            .with_status_aggregator(SimpleStatusAggregator::new(vec![
+                HealthStatus::Down,
+                degraded.clone(),
+                HealthStatus::Up,
+                HealthStatus::Unknown,
+            ]))
+            .build();
+        assert_eq!(health.details().await.status, degraded);
+    }
+
+    #[tokio::test]
+    async fn test_http_mapper() {
+        let router = Router::new().route("/health", get(health)).layer(
+            Health::builder()
+                .with_http_mapper(
+                    SimpleHttpCodeStatusMapper::default()
+                        .with_mapping(HealthStatus::Unknown, StatusCode::INTERNAL_SERVER_ERROR),
+                )
+                .with_indicator(MockHealthIndicator::new(
+                    "unknown".to_string(),
+                    HealthDetail::new(HealthStatus::Unknown),
+                ))
+                .build(),
+        );
+
+        let server = TestServer::new(router).unwrap();
+        let response = server.get("/health").await;
+
+        assert_eq!(response.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            response.json::<HealthDetails>().status,
+            HealthStatus::Unknown
+        );
+    }
+
+    #[tokio::test]
+    async fn test_groups() {
+        let mut down = HealthDetail::down();
+        down.with_detail("error".to_owned(), "connection refused".to_owned());
+
+        let router = Router::new()
+            .route("/health", get(health))
+            .route("/health/{group}", get(health_group))
+            .layer(
+                Health::builder()
+                    .with_indicator(MockHealthIndicator::new(
+                        "ping".to_string(),
+                        HealthDetail::up(),
+                    ))
+                    .with_indicator(MockHealthIndicator::new("db".to_string(), down.clone()))
+                    .with_group(HealthGroup::new("liveness".to_owned()).include("ping".to_owned()))
+                    .with_group(
+                        HealthGroup::new("readiness".to_owned())
+                            .exclude("ping".to_owned())
+                            .show_details(false),
+                    )
+                    .with_group(
+                        HealthGroup::new("quiet".to_owned())
+                            .show_components(false)
+                            .with_http_mapper(SimpleHttpCodeStatusMapper::new([])),
+                    )
+                    .build(),
+            );
+
+        let server = TestServer::new(router).unwrap();
+
+        let response = server.get("/health/liveness").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            without_durations(response.json::<HealthDetails>()),
+            HealthDetails {
+                status: HealthStatus::Up,
+                components: BTreeMap::from_iter([("ping".to_owned(), HealthDetail::up())]),
+            }
+        );
+
+        let response = server.get("/health/readiness").await;
+        assert_eq!(response.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            without_durations(response.json::<HealthDetails>()),
+            HealthDetails {
+                status: HealthStatus::Down,
+                components: BTreeMap::from_iter([("db".to_owned(), HealthDetail::down())]),
+            }
+        );
+
+        let response = server.get("/health/quiet").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            without_durations(response.json::<HealthDetails>()),
+            HealthDetails {
+                status: HealthStatus::Down,
+                components: Default::default(),
+            }
+        );
+
+        let response = server.get("/health/missing").await;
+        assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+        let response = server.get("/health").await;
+        assert_eq!(
+            without_durations(response.json::<HealthDetails>()).components["db"],
+            down
+        );
+    }
+
+    #[tokio::test]
+    async fn test_component() {
+        let router = Router::new()
+            .route("/health/{component}", get(health_component))
+            .layer(
+                Health::builder()
+                    .with_indicator(MockHealthIndicator::new(
+                        "upper".to_string(),
+                        HealthDetail::up(),
+                    ))
+                    .with_indicator(MockHealthIndicator::new(
+                        "downer".to_string(),
+                        HealthDetail::down(),
+                    ))
+                    .build(),
+            );
+
+        let server = TestServer::new(router).unwrap();
+
+        let response = server.get("/health/upper").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            without_duration(response.json::<HealthDetail>()),
+            HealthDetail::up()
+        );
+
+        let response = server.get("/health/downer").await;
+        assert_eq!(response.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            without_duration(response.json::<HealthDetail>()),
+            HealthDetail::down()
+        );
+
+        let response = server.get("/health/missing").await;
+        assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_router() {
+        let health = Health::builder()
+            .with_indicator(MockHealthIndicator::new(
+                "db".to_string(),
+                HealthDetail::down(),
+            ))
+            .with_group(HealthGroup::new("liveness".to_owned()).exclude("db".to_owned()))
+            .build();
+        let router = Router::new().merge(health.router());
+
+        let server = TestServer::new(router).unwrap();
+
+        let response = server.get("/health").await;
+        assert_eq!(response.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let response = server.get("/health/liveness").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            without_durations(response.json::<HealthDetails>()),
+            HealthDetails {
+                status: HealthStatus::Up,
+                components: Default::default(),
+            }
+        );
+
+        let response = server.get("/health/db").await;
+        assert_eq!(response.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            without_duration(response.json::<HealthDetail>()),
+            HealthDetail::down()
+        );
+
+        let response = server.get("/health/missing").await;
+        assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_structured_details() {
+        #[derive(Serialize)]
+        struct Pool {
+            size: u32,
+            idle: u32,
+        }
+
+        let mut detail = HealthDetail::up();
+        detail
+            .with_detail("database".to_owned(), "PostgreSQL")
+            .with_detail("latency_ms".to_owned(), 3)
+            .with_detail("read_only".to_owned(), false)
+            .with_serialized_detail("pool".to_owned(), &Pool { size: 4, idle: 3 })
+            .unwrap();
+
+        let json = serde_json::json!({
+            "status": "UP",
+            "details": {
+                "database": "PostgreSQL",
+                "latency_ms": 3,
+                "read_only": false,
+                "pool": {
+                    "size": 4,
+                    "idle": 3
+                }
+            }
+        });
+
+        assert_eq!(serde_json::to_value(&detail).unwrap(), json);
+        assert_eq!(
+            serde_json::from_value::<HealthDetail>(json).unwrap(),
+            detail
+        );
+    }
+
+    struct CountingHealthIndicator(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl HealthIndicator for CountingHealthIndicator {
+        fn name(&self) -> String {
+            "counting".to_owned()
+        }
+
+        async fn details(&self) -> HealthDetail {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            HealthDetail::up()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cache() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let health = Health::builder()
+            .with_cache_ttl(Duration::from_secs(10))
+            .with_indicator(CountingHealthIndicator(count.clone()))
+            .with_indicator(MockHealthIndicator::new(
+                "uncached".to_string(),
+                HealthDetail::up(),
+            ))
+            .with_indicator_cache_ttl("uncached".to_owned(), Duration::ZERO)
+            .build();
+
+        let first = health.details().await;
+        let second = health.details().await;
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert_eq!(first, second);
+        assert!(first.components["counting"]
+            .details
+            .contains_key("checked_at"));
+        assert_eq!(
+            without_duration(first.components["uncached"].clone()),
+            HealthDetail::up()
+        );
+
+        tokio::time::advance(Duration::from_secs(11)).await;
+        health.details().await;
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_background_refresh() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let health = Health::builder()
+            .with_cache_ttl(Duration::from_secs(60))
+            .with_indicator(CountingHealthIndicator(count.clone()))
+            .build();
+
+        let refresh = health.spawn_refresh(Duration::from_secs(1));
+
+        tokio::time::sleep(Duration::from_millis(3500)).await;
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+
+        health.details().await;
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+
+        drop(health);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert!(refresh.is_finished());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_refresh() {
+        let health = Health::builder()
+            .with_cache_ttl(Duration::from_secs(60))
+            .with_indicator(
+                MockHealthIndicator::new("slow".to_string(), HealthDetail::up())
+                    .with_delay(Duration::from_secs(5)),
+            )
+            .build();
+        let stale = health.details().await;
+
+        tokio::time::advance(Duration::from_secs(61)).await;
+        let refresh = tokio::spawn({
+            let health = health.clone();
+            async move { health.refresh().await }
+        });
+        tokio::task::yield_now().await;
+
+        // The refresh is still running, so the expired snapshot is served right away.
+        let start = tokio::time::Instant::now();
+        assert_eq!(health.details().await, stale);
+        assert_eq!(
+            health.component_details("slow").await.unwrap(),
+            stale.components["slow"]
+        );
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        refresh.await.unwrap();
+        let start = tokio::time::Instant::now();
+        health.details().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_duration() {
+        let health = Health::builder()
+            .with_indicator(
+                MockHealthIndicator::new("slow".to_string(), HealthDetail::up())
+                    .with_delay(Duration::from_millis(250)),
+            )
+            .build();
+
+        let details = health.details().await;
+        assert_eq!(details.components["slow"].details["duration_ms"], 250);
+
+        let detail = health.component_details("slow").await.unwrap();
+        assert_eq!(detail.details["duration_ms"], 250);
+    }
 }
@@ -0,0 +1,260 @@
+use crate::{HealthDetail, HealthIndicator, HealthStatus};
+use async_trait::async_trait;
+use std::future::Future;
+use std::io;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::time::Instant;
+
+/// The longest banner read from a socket, in bytes.
+const MAX_BANNER_LEN: usize = 512;
+
+/// Where a [SocketHealthIndicator] connects to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketTarget {
+    /// A `host:port` pair, the host is resolved on every check.
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl SocketTarget {
+    fn describe(&self) -> String {
+        match self {
+            SocketTarget::Tcp(address) => address.clone(),
+            #[cfg(unix)]
+            SocketTarget::Unix(path) => path.to_string_lossy().into_owned(),
+        }
+    }
+}
+
+/// Checks that a dependency without a native client, like an SMTP relay or a sidecar, accepts
+/// connections, reporting the `target`, the resolved `address` and the `latency_ms` of the
+/// connection. The connection is closed right away unless a banner is expected.
+///
+/// ```rust
+/// # use axum_health::socket::{SocketHealthIndicator, SocketTarget};
+/// # use std::time::Duration;
+/// SocketHealthIndicator::new("smtp".to_owned(), SocketTarget::Tcp("localhost:25".to_owned()))
+///     .with_timeout(Duration::from_secs(2))
+///     .with_banner("220 ".to_owned());
+/// ```
+pub struct SocketHealthIndicator {
+    name: String,
+    target: SocketTarget,
+    timeout: Duration,
+    banner: Option<String>,
+}
+
+impl SocketHealthIndicator {
+    pub fn new(name: String, target: SocketTarget) -> Self {
+        SocketHealthIndicator {
+            name,
+            target,
+            timeout: Duration::from_secs(5),
+            banner: None,
+        }
+    }
+
+    /// Fails the check when connecting, and reading the banner if any, takes longer than
+    /// `timeout`, defaults to 5 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Reads the first line sent by the server, reporting it as `banner`, and fails the check
+    /// unless it starts with `prefix`.
+    pub fn with_banner(mut self, prefix: String) -> Self {
+        self.banner = Some(prefix);
+        self
+    }
+
+    async fn check(&self, detail: &mut HealthDetail, deadline: Instant) -> io::Result<()> {
+        match &self.target {
+            SocketTarget::Tcp(address) => {
+                let stream =
+                    with_deadline(deadline, tokio::net::TcpStream::connect(address)).await?;
+                detail.with_detail("address".to_owned(), stream.peer_addr()?.to_string());
+                self.check_banner(detail, stream, deadline).await
+            }
+            #[cfg(unix)]
+            SocketTarget::Unix(path) => {
+                let stream = with_deadline(deadline, tokio::net::UnixStream::connect(path)).await?;
+                detail.with_detail("address".to_owned(), path.to_string_lossy());
+                self.check_banner(detail, stream, deadline).await
+            }
+        }
+    }
+
+    async fn check_banner<S>(
+        &self,
+        detail: &mut HealthDetail,
+        mut stream: S,
+        deadline: Instant,
+    ) -> io::Result<()>
+    where
+        S: AsyncRead + Unpin,
+    {
+        let Some(prefix) = &self.banner else {
+            return Ok(());
+        };
+
+        let mut banner = Vec::new();
+        with_deadline(deadline, async {
+            let mut buffer = [0; MAX_BANNER_LEN];
+            while banner.len() < MAX_BANNER_LEN && !banner.contains(&b'\n') {
+                let read = stream
+                    .read(&mut buffer[..MAX_BANNER_LEN - banner.len()])
+                    .await?;
+                if read == 0 {
+                    break;
+                }
+                banner.extend_from_slice(&buffer[..read]);
+            }
+            Ok(())
+        })
+        .await?;
+
+        let banner = String::from_utf8_lossy(&banner);
+        let banner = banner.lines().next().unwrap_or_default();
+        detail.with_detail("banner".to_owned(), banner);
+        if banner.starts_with(prefix.as_str()) {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the banner does not start with {prefix:?}"),
+            ))
+        }
+    }
+}
+
+#[async_trait]
+impl HealthIndicator for SocketHealthIndicator {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        let mut detail = HealthDetail::up();
+        let start = Instant::now();
+        if let Err(error) = self.check(&mut detail, start + self.timeout).await {
+            detail.status = HealthStatus::Down;
+            detail.with_detail("error".to_owned(), error.to_string());
+        }
+        detail.with_detail("latency_ms".to_owned(), start.elapsed().as_millis() as u64);
+        detail.with_detail("target".to_owned(), self.target.describe());
+        detail
+    }
+}
+
+async fn with_deadline<T>(
+    deadline: Instant,
+    future: impl Future<Output = io::Result<T>>,
+) -> io::Result<T> {
+    match tokio::time::timeout_at(deadline, future).await {
+        Ok(result) => result,
+        Err(_) => Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            "the connection timed out",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::socket::{SocketHealthIndicator, SocketTarget};
+    use crate::{HealthIndicator, HealthStatus};
+    use std::time::Duration;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    /// Accepts connections on a local port, greeting each with `banner` and keeping it open.
+    async fn listen(banner: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let _ = socket.write_all(banner.as_bytes()).await;
+                sockets.push(socket);
+            }
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn test_tcp() {
+        let address = listen("220 smtp.example.com ESMTP\r\n").await;
+        let indicator = |banner: &str| {
+            SocketHealthIndicator::new("smtp".to_owned(), SocketTarget::Tcp(address.clone()))
+                .with_banner(banner.to_owned())
+        };
+
+        let detail = indicator("220 ").details().await;
+        assert_eq!(detail.status, HealthStatus::Up);
+        assert_eq!(detail.details["target"], address);
+        assert_eq!(detail.details["address"], address);
+        assert_eq!(detail.details["banner"], "220 smtp.example.com ESMTP");
+        assert!(detail.details["latency_ms"].is_u64());
+
+        let detail = indicator("554 ").details().await;
+        assert_eq!(detail.status, HealthStatus::Down);
+        assert_eq!(detail.details["banner"], "220 smtp.example.com ESMTP");
+        assert!(detail.details.contains_key("error"));
+    }
+
+    #[tokio::test]
+    async fn test_tcp_down() {
+        // Nothing listens on the port once the listener is dropped.
+        let address = {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap().to_string()
+        };
+
+        let detail = SocketHealthIndicator::new("tcp".to_owned(), SocketTarget::Tcp(address))
+            .details()
+            .await;
+        assert_eq!(detail.status, HealthStatus::Down);
+        assert!(detail.details.contains_key("error"));
+        assert!(!detail.details.contains_key("address"));
+    }
+
+    #[tokio::test]
+    async fn test_banner_timeout() {
+        let address = listen("").await;
+
+        let detail = SocketHealthIndicator::new("tcp".to_owned(), SocketTarget::Tcp(address))
+            .with_timeout(Duration::from_millis(50))
+            .with_banner("220 ".to_owned())
+            .details()
+            .await;
+        assert_eq!(detail.status, HealthStatus::Down);
+        assert_eq!(detail.details["error"], "the connection timed out");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sidecar.sock");
+        let _listener = tokio::net::UnixListener::bind(&path).unwrap();
+
+        let indicator =
+            SocketHealthIndicator::new("sidecar".to_owned(), SocketTarget::Unix(path.clone()));
+        let detail = indicator.details().await;
+        assert_eq!(detail.status, HealthStatus::Up);
+        assert_eq!(detail.details["address"], path.to_str().unwrap());
+
+        let detail = SocketHealthIndicator::new(
+            "sidecar".to_owned(),
+            SocketTarget::Unix(dir.path().join("missing.sock")),
+        )
+        .details()
+        .await;
+        assert_eq!(detail.status, HealthStatus::Down);
+    }
+}
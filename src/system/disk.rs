@@ -0,0 +1,142 @@
+use crate::{HealthDetail, HealthIndicator};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// The free space below which a [DiskSpaceHealthIndicator] reports
+/// [HealthStatus::Down](crate::HealthStatus::Down).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiskSpaceThreshold {
+    Bytes(u64),
+    /// A percentage of the total space, e.g. `10.0` for 10%.
+    Percent(f64),
+}
+
+impl DiskSpaceThreshold {
+    fn bytes(&self, total: u64) -> u64 {
+        match self {
+            DiskSpaceThreshold::Bytes(bytes) => *bytes,
+            DiskSpaceThreshold::Percent(percent) => (total as f64 * percent / 100.0) as u64,
+        }
+    }
+}
+
+/// Checks the free space of the filesystem containing `path`, like Spring's
+/// `DiskSpaceHealthIndicator`, reporting `total`, `free` and `threshold` in bytes along with the
+/// `path`. Several paths can be checked by grouping indicators in a
+/// [CompositeHealthIndicator](crate::CompositeHealthIndicator).
+///
+/// ```rust
+/// # use axum_health::system::{DiskSpaceHealthIndicator, DiskSpaceThreshold};
+/// # use axum_health::CompositeHealthIndicator;
+/// CompositeHealthIndicator::new("disk".to_owned())
+///     .with_indicator(DiskSpaceHealthIndicator::new(
+///         "uploads".to_owned(),
+///         "/var/lib/uploads".into(),
+///         DiskSpaceThreshold::Percent(10.0),
+///     ))
+///     .with_indicator(DiskSpaceHealthIndicator::new(
+///         "sqlite".to_owned(),
+///         "/var/lib/sqlite".into(),
+///         DiskSpaceThreshold::Bytes(1024 * 1024 * 1024),
+///     ));
+/// ```
+pub struct DiskSpaceHealthIndicator {
+    name: String,
+    path: PathBuf,
+    threshold: DiskSpaceThreshold,
+}
+
+impl DiskSpaceHealthIndicator {
+    pub fn new(name: String, path: PathBuf, threshold: DiskSpaceThreshold) -> Self {
+        DiskSpaceHealthIndicator {
+            name,
+            path,
+            threshold,
+        }
+    }
+}
+
+#[async_trait]
+impl HealthIndicator for DiskSpaceHealthIndicator {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        // statvfs can hang on an unresponsive network filesystem.
+        let path = self.path.clone();
+        let stats = match tokio::task::spawn_blocking(move || fs4::statvfs(path)).await {
+            Ok(stats) => stats,
+            Err(error) => Err(error.into()),
+        };
+
+        let mut detail = match stats {
+            Ok(stats) => {
+                let total = stats.total_space();
+                let free = stats.available_space();
+                let threshold = self.threshold.bytes(total);
+
+                let mut detail = if free >= threshold {
+                    HealthDetail::up()
+                } else {
+                    HealthDetail::down()
+                };
+                detail.with_detail("total".to_owned(), total);
+                detail.with_detail("free".to_owned(), free);
+                detail.with_detail("threshold".to_owned(), threshold);
+                detail
+            }
+            Err(error) => {
+                let mut detail = HealthDetail::down();
+                detail.with_detail("error".to_owned(), error.to_string());
+                detail
+            }
+        };
+        detail.with_detail("path".to_owned(), self.path.to_string_lossy());
+        detail
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::system::{DiskSpaceHealthIndicator, DiskSpaceThreshold};
+    use crate::{HealthIndicator, HealthStatus};
+
+    #[tokio::test]
+    async fn test_disk_space() {
+        let dir = tempfile::tempdir().unwrap();
+        let indicator = |threshold| {
+            DiskSpaceHealthIndicator::new("disk".to_owned(), dir.path().to_owned(), threshold)
+        };
+
+        let detail = indicator(DiskSpaceThreshold::Bytes(0)).details().await;
+        assert_eq!(detail.status, HealthStatus::Up);
+        assert_eq!(detail.details["threshold"], 0);
+        assert_eq!(detail.details["path"], dir.path().to_str().unwrap());
+        let total = detail.details["total"].as_u64().unwrap();
+        let free = detail.details["free"].as_u64().unwrap();
+        assert!(total > 0 && free <= total);
+
+        let detail = indicator(DiskSpaceThreshold::Bytes(u64::MAX))
+            .details()
+            .await;
+        assert_eq!(detail.status, HealthStatus::Down);
+
+        let detail = indicator(DiskSpaceThreshold::Percent(50.0)).details().await;
+        assert_eq!(detail.details["threshold"], total / 2);
+    }
+
+    #[tokio::test]
+    async fn test_missing_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let indicator = DiskSpaceHealthIndicator::new(
+            "disk".to_owned(),
+            dir.path().join("missing"),
+            DiskSpaceThreshold::Bytes(0),
+        );
+
+        let detail = indicator.details().await;
+        assert_eq!(detail.status, HealthStatus::Down);
+        assert!(detail.details.contains_key("error"));
+    }
+}
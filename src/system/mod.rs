@@ -0,0 +1,11 @@
+#[cfg(feature = "disk-space")]
+mod disk;
+#[cfg(target_os = "linux")]
+mod process;
+mod runtime;
+
+#[cfg(feature = "disk-space")]
+pub use disk::{DiskSpaceHealthIndicator, DiskSpaceThreshold};
+#[cfg(target_os = "linux")]
+pub use process::ProcessHealthIndicator;
+pub use runtime::RuntimeHealthIndicator;
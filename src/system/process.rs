@@ -0,0 +1,251 @@
+use crate::threshold::Thresholds;
+use crate::{HealthDetail, HealthIndicator, HealthStatus};
+use async_trait::async_trait;
+use std::io;
+
+/// The usage reported as `DEGRADED` when no threshold is passed.
+const DEFAULT_THRESHOLD: f64 = 0.9;
+
+/// Reports the resident memory and the open file descriptors of the process, read from
+/// `/proc/self`, against their limits. The limits default to the memory limit of the cgroup of the
+/// process and its soft `RLIMIT_NOFILE`, an unlimited resource is only reported.
+///
+/// Without thresholds, a usage of 90% of either limit is reported as `DEGRADED`.
+///
+/// ```rust
+/// # use axum_health::system::ProcessHealthIndicator;
+/// # use axum_health::HealthStatus;
+/// ProcessHealthIndicator::new("process".to_owned())
+///     .with_threshold(0.8, HealthStatus::degraded())
+///     .with_threshold(0.95, HealthStatus::Down);
+/// ```
+pub struct ProcessHealthIndicator {
+    name: String,
+    max_rss: Option<u64>,
+    max_open_files: Option<u64>,
+    thresholds: Thresholds<f64>,
+}
+
+impl ProcessHealthIndicator {
+    pub fn new(name: String) -> Self {
+        ProcessHealthIndicator {
+            name,
+            max_rss: None,
+            max_open_files: None,
+            thresholds: Thresholds::with_default(DEFAULT_THRESHOLD, HealthStatus::degraded()),
+        }
+    }
+
+    /// Compares the resident memory to `bytes` instead of the memory limit of the cgroup.
+    pub fn with_max_rss(mut self, bytes: u64) -> Self {
+        self.max_rss = Some(bytes);
+        self
+    }
+
+    /// Compares the open file descriptors to `files` instead of `RLIMIT_NOFILE`.
+    pub fn with_max_open_files(mut self, files: u64) -> Self {
+        self.max_open_files = Some(files);
+        self
+    }
+
+    /// Reports `status` instead of [HealthStatus::Up] when the usage of either resource is at or
+    /// above `threshold`, a fraction of its limit, replacing the default threshold. The status of
+    /// the highest threshold reached applies.
+    pub fn with_threshold(mut self, threshold: f64, status: HealthStatus) -> Self {
+        self.thresholds.push(threshold, status);
+        self
+    }
+
+    fn check(&self, detail: &mut HealthDetail) -> io::Result<Option<f64>> {
+        let limits = std::fs::read_to_string("/proc/self/limits")?;
+        // Linux ignores RLIMIT_RSS, the memory of a process is only limited by its cgroup.
+        let max_rss = self.max_rss.or_else(cgroup_memory_limit);
+        let max_open_files = self
+            .max_open_files
+            .or(soft_limit(&limits, "Max open files"));
+
+        let status = std::fs::read_to_string("/proc/self/status")?;
+        let rss = status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))
+            .and_then(|value| value.trim().strip_suffix(" kB"))
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(|kilobytes| kilobytes * 1024)
+            .ok_or_else(|| io::Error::other("VmRSS is missing from /proc/self/status"))?;
+
+        // Reading the directory opens one more descriptor, which is not counted.
+        let open_files = (std::fs::read_dir("/proc/self/fd")?.count() as u64).saturating_sub(1);
+
+        detail.with_detail("rss".to_owned(), rss);
+        detail.with_detail("max_rss".to_owned(), max_rss);
+        detail.with_detail("open_files".to_owned(), open_files);
+        detail.with_detail("max_open_files".to_owned(), max_open_files);
+
+        let usage = [(rss, max_rss), (open_files, max_open_files)]
+            .into_iter()
+            .filter_map(|(used, max)| {
+                max.filter(|max| *max > 0)
+                    .map(|max| used as f64 / max as f64)
+            })
+            .reduce(f64::max);
+        Ok(usage)
+    }
+}
+
+#[async_trait]
+impl HealthIndicator for ProcessHealthIndicator {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        let mut detail = HealthDetail::up();
+        match self.check(&mut detail) {
+            Ok(Some(usage)) => {
+                if let Some((_, status)) = self.thresholds.find(|threshold| usage >= *threshold) {
+                    detail.status = status.clone();
+                }
+            }
+            Ok(None) => {}
+            Err(error) => {
+                detail.status = HealthStatus::Down;
+                detail.with_detail("error".to_owned(), error.to_string());
+            }
+        }
+        detail
+    }
+}
+
+/// Finds the soft limit of `resource` in `/proc/self/limits`, [None] when it is unlimited.
+fn soft_limit(limits: &str, resource: &str) -> Option<u64> {
+    limits
+        .lines()
+        .find_map(|line| line.strip_prefix(resource))
+        .and_then(|line| line.split_whitespace().next())
+        .and_then(|soft| soft.parse().ok())
+}
+
+/// The memory limit of the cgroup of the process, [None] when it is unlimited or unknown.
+fn cgroup_memory_limit() -> Option<u64> {
+    let cgroups = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    let candidates = match memory_cgroup(&cgroups)? {
+        (MemoryController::V1, path) => [
+            format!("/sys/fs/cgroup/memory{path}/memory.limit_in_bytes"),
+            "/sys/fs/cgroup/memory/memory.limit_in_bytes".to_owned(),
+        ],
+        (MemoryController::V2, path) => [
+            format!("/sys/fs/cgroup{path}/memory.max"),
+            "/sys/fs/cgroup/memory.max".to_owned(),
+        ],
+    };
+    // Without a cgroup namespace, a container only mounts its own cgroup at the root.
+    candidates
+        .iter()
+        .find_map(|file| std::fs::read_to_string(file).ok())
+        .and_then(|limit| memory_limit(&limit))
+}
+
+#[derive(Debug, PartialEq)]
+enum MemoryController {
+    V1,
+    V2,
+}
+
+/// Finds the cgroup of the memory controller in `/proc/self/cgroup`, preferring a cgroup v1
+/// memory hierarchy over the unified one on hybrid systems.
+fn memory_cgroup(cgroups: &str) -> Option<(MemoryController, &str)> {
+    let mut unified = None;
+    for line in cgroups.lines() {
+        let mut fields = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if controllers
+            .split(',')
+            .any(|controller| controller == "memory")
+        {
+            return Some((MemoryController::V1, path));
+        }
+        if id == "0" && controllers.is_empty() {
+            unified = Some((MemoryController::V2, path));
+        }
+    }
+    unified
+}
+
+/// Parses a cgroup memory limit, cgroup v2 writes `max` for an unlimited memory and cgroup v1 a
+/// value close to `i64::MAX`.
+fn memory_limit(limit: &str) -> Option<u64> {
+    limit
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|limit| *limit <= i64::MAX as u64 / 2)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::system::process::{memory_cgroup, memory_limit, soft_limit, MemoryController};
+    use crate::system::ProcessHealthIndicator;
+    use crate::{HealthIndicator, HealthStatus};
+
+    #[test]
+    fn test_soft_limit() {
+        let limits = "\
+Limit                     Soft Limit           Hard Limit           Units
+Max resident set          unlimited            unlimited            bytes
+Max open files            1024                 1048576              files
+";
+        assert_eq!(soft_limit(limits, "Max resident set"), None);
+        assert_eq!(soft_limit(limits, "Max open files"), Some(1024));
+    }
+
+    #[test]
+    fn test_memory_cgroup() {
+        assert_eq!(
+            memory_cgroup("0::/system.slice/app.service\n"),
+            Some((MemoryController::V2, "/system.slice/app.service"))
+        );
+        let hybrid = "5:cpu,cpuacct:/\n4:memory:/docker/abc\n0::/\n";
+        assert_eq!(
+            memory_cgroup(hybrid),
+            Some((MemoryController::V1, "/docker/abc"))
+        );
+        assert_eq!(memory_cgroup(""), None);
+
+        assert_eq!(memory_limit("536870912\n"), Some(536870912));
+        assert_eq!(memory_limit("max\n"), None);
+        assert_eq!(memory_limit("9223372036854771712\n"), None);
+    }
+
+    #[tokio::test]
+    async fn test_process() {
+        let detail = ProcessHealthIndicator::new("process".to_owned())
+            .details()
+            .await;
+        assert_eq!(detail.status, HealthStatus::Up);
+        assert!(detail.details["rss"].as_u64().unwrap() > 0);
+        assert!(detail.details["open_files"].as_u64().unwrap() > 0);
+
+        let degraded = HealthStatus::degraded();
+        let indicator = |max_open_files| {
+            ProcessHealthIndicator::new("process".to_owned())
+                .with_max_open_files(max_open_files)
+                .with_threshold(1.0, HealthStatus::Down)
+                .with_threshold(0.0, degraded.clone())
+        };
+        assert_eq!(indicator(u64::MAX).details().await.status, degraded);
+
+        let detail = indicator(1).details().await;
+        assert_eq!(detail.status, HealthStatus::Down);
+        assert_eq!(detail.details["max_open_files"], 1);
+
+        let detail = ProcessHealthIndicator::new("process".to_owned())
+            .with_max_rss(1)
+            .details()
+            .await;
+        assert_eq!(detail.status, degraded);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::threshold::Thresholds;
+use crate::{HealthDetail, HealthIndicator, HealthStatus};
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+
+/// The share of the time between two checks a worker has to spend busy to be counted as busy.
+#[cfg(target_has_atomic = "64")]
+const BUSY_RATIO: f64 = 0.9;
+
+/// The scheduling latency reported as `DEGRADED` when no threshold is passed.
+const DEFAULT_THRESHOLD: Duration = Duration::from_millis(100);
+
+/// Reports the metrics of the Tokio runtime running the check: the number of `workers`, of
+/// `alive_tasks` and of tasks in the global queue (`global_queue_depth`). From the second check
+/// on, `busy_workers` counts the workers that spent nearly all the time since the previous check
+/// running tasks.
+///
+/// The `scheduling_latency_us` detail measures how long a spawned no-op task waits before it is
+/// polled, which grows as the runtime saturates or its workers are blocked. Thresholds on the
+/// latency turn it into a status, since a starved runtime cannot serve requests in time. Without
+/// thresholds, a latency above 100ms is reported as `DEGRADED`.
+///
+/// A runtime whose workers were all busy since the previous check is saturated, and reported as
+/// `DEGRADED` as well.
+///
+/// ```rust
+/// # use axum_health::system::RuntimeHealthIndicator;
+/// # use axum_health::HealthStatus;
+/// # use std::time::Duration;
+/// RuntimeHealthIndicator::new("runtime".to_owned())
+///     .with_threshold(Duration::from_millis(50), HealthStatus::degraded())
+///     .with_threshold(Duration::from_secs(1), HealthStatus::Down);
+/// ```
+pub struct RuntimeHealthIndicator {
+    name: String,
+    thresholds: Thresholds<Duration>,
+    /// When the previous check ran and the total busy duration of every worker at that time.
+    previous: Mutex<Option<(Instant, Vec<Duration>)>>,
+}
+
+impl RuntimeHealthIndicator {
+    pub fn new(name: String) -> Self {
+        RuntimeHealthIndicator {
+            name,
+            thresholds: Thresholds::with_default(DEFAULT_THRESHOLD, HealthStatus::degraded()),
+            previous: Mutex::new(None),
+        }
+    }
+
+    /// Reports `status` instead of [HealthStatus::Up] when the scheduling latency is above
+    /// `threshold`, replacing the default 100ms. With several thresholds, the highest one exceeded
+    /// decides the status.
+    pub fn with_threshold(mut self, threshold: Duration, status: HealthStatus) -> Self {
+        self.thresholds.push(threshold, status);
+        self
+    }
+
+    #[cfg(target_has_atomic = "64")]
+    fn busy_workers(&self, handle: &Handle) -> Option<usize> {
+        let metrics = handle.metrics();
+        let now = Instant::now();
+        let busy = (0..metrics.num_workers())
+            .map(|worker| metrics.worker_total_busy_duration(worker))
+            .collect::<Vec<_>>();
+
+        let mut previous = self
+            .previous
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let busy_workers = previous
+            .as_ref()
+            .filter(|(_, previous_busy)| previous_busy.len() == busy.len())
+            .map(|(previous_now, previous_busy)| {
+                let elapsed = now.duration_since(*previous_now).as_secs_f64();
+                busy.iter()
+                    .zip(previous_busy)
+                    .filter(|(busy, previous_busy)| {
+                        elapsed > 0.0
+                            && busy.saturating_sub(**previous_busy).as_secs_f64() / elapsed
+                                >= BUSY_RATIO
+                    })
+                    .count()
+            });
+        *previous = Some((now, busy));
+        busy_workers
+    }
+
+    #[cfg(not(target_has_atomic = "64"))]
+    fn busy_workers(&self, _handle: &Handle) -> Option<usize> {
+        None
+    }
+}
+
+#[async_trait]
+impl HealthIndicator for RuntimeHealthIndicator {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        let handle = Handle::current();
+        let metrics = handle.metrics();
+
+        let mut detail = HealthDetail::up();
+        detail.with_detail("workers".to_owned(), metrics.num_workers());
+        detail.with_detail("alive_tasks".to_owned(), metrics.num_alive_tasks());
+        detail.with_detail(
+            "global_queue_depth".to_owned(),
+            metrics.global_queue_depth(),
+        );
+        let busy_workers = self.busy_workers(&handle);
+        if let Some(busy_workers) = busy_workers {
+            detail.with_detail("busy_workers".to_owned(), busy_workers);
+        }
+
+        let spawned = Instant::now();
+        match handle.spawn(async move { spawned.elapsed() }).await {
+            Ok(latency) => {
+                detail.with_detail(
+                    "scheduling_latency_us".to_owned(),
+                    latency.as_micros() as u64,
+                );
+                if let Some((_, status)) = self.thresholds.find(|threshold| latency > *threshold) {
+                    detail.status = status.clone();
+                }
+                if detail.status == HealthStatus::Up
+                    && busy_workers.is_some_and(|busy| busy == metrics.num_workers())
+                {
+                    detail.status = HealthStatus::degraded();
+                }
+            }
+            Err(error) => {
+                detail.status = HealthStatus::Down;
+                detail.with_detail("error".to_owned(), error.to_string());
+            }
+        }
+        detail
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::system::RuntimeHealthIndicator;
+    use crate::{HealthIndicator, HealthStatus};
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_runtime() {
+        let indicator = RuntimeHealthIndicator::new("runtime".to_owned());
+
+        let detail = indicator.details().await;
+        assert_eq!(detail.status, HealthStatus::Up);
+        assert_eq!(detail.details["workers"], 2);
+        assert!(detail.details["alive_tasks"].is_u64());
+        assert!(detail.details["global_queue_depth"].is_u64());
+        assert!(detail.details["scheduling_latency_us"].is_u64());
+        assert!(!detail.details.contains_key("busy_workers"));
+
+        let detail = indicator.details().await;
+        assert!(detail.details["busy_workers"].as_u64().unwrap() <= 2);
+    }
+
+    #[tokio::test]
+    async fn test_runtime_threshold() {
+        let degraded = HealthStatus::degraded();
+        let indicator = RuntimeHealthIndicator::new("runtime".to_owned())
+            .with_threshold(Duration::from_secs(60), HealthStatus::Down)
+            .with_threshold(Duration::ZERO, degraded.clone());
+
+        assert_eq!(indicator.details().await.status, degraded);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_runtime_saturated() {
+        let indicator = RuntimeHealthIndicator::new("runtime".to_owned());
+        assert_eq!(indicator.details().await.status, HealthStatus::Up);
+
+        // Blocking the only worker keeps it busy until the next check.
+        tokio::spawn(async { std::thread::sleep(Duration::from_millis(200)) })
+            .await
+            .unwrap();
+        let detail = indicator.details().await;
+        assert_eq!(detail.details["busy_workers"], 1);
+        assert_eq!(detail.status, HealthStatus::degraded());
+    }
+}
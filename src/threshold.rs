@@ -0,0 +1,70 @@
+use crate::HealthStatus;
+use std::cmp::Ordering;
+
+/// Statuses reported once a measure passes a threshold, the status of the highest threshold
+/// passed applies. A default threshold only applies until the first one is added.
+pub(crate) struct Thresholds<T> {
+    thresholds: Vec<(T, HealthStatus)>,
+    default: bool,
+}
+
+impl<T: PartialOrd> Thresholds<T> {
+    pub(crate) fn new() -> Self {
+        Thresholds {
+            thresholds: Vec::new(),
+            default: false,
+        }
+    }
+
+    pub(crate) fn with_default(threshold: T, status: HealthStatus) -> Self {
+        Thresholds {
+            thresholds: vec![(threshold, status)],
+            default: true,
+        }
+    }
+
+    pub(crate) fn push(&mut self, threshold: T, status: HealthStatus) {
+        if self.default {
+            self.thresholds.clear();
+            self.default = false;
+        }
+        self.thresholds.push((threshold, status));
+        self.thresholds
+            .sort_by(|(left, _), (right, _)| left.partial_cmp(right).unwrap_or(Ordering::Equal));
+    }
+
+    /// Finds the highest threshold for which `passed` holds.
+    pub(crate) fn find(&self, passed: impl Fn(&T) -> bool) -> Option<&(T, HealthStatus)> {
+        self.thresholds
+            .iter()
+            .rev()
+            .find(|(threshold, _)| passed(threshold))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::threshold::Thresholds;
+    use crate::HealthStatus;
+
+    #[test]
+    fn test_thresholds() {
+        let mut thresholds = Thresholds::with_default(0.9, HealthStatus::degraded());
+        assert_eq!(
+            thresholds.find(|threshold| 0.95 >= *threshold),
+            Some(&(0.9, HealthStatus::degraded()))
+        );
+
+        thresholds.push(1.0, HealthStatus::Down);
+        thresholds.push(0.5, HealthStatus::OutOfService);
+        assert_eq!(
+            thresholds.find(|threshold| 0.95 >= *threshold).unwrap().0,
+            0.5
+        );
+        assert_eq!(
+            thresholds.find(|threshold| 1.0 >= *threshold).unwrap().0,
+            1.0
+        );
+        assert_eq!(thresholds.find(|threshold| 0.1 >= *threshold), None);
+    }
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "http")]
+
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Router;
+use axum_health::http::HttpHealthIndicator;
+use axum_health::service::{Health, HealthDetail, HealthIndicator, HealthStatus};
+use reqwest::Method;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+#[tokio::test]
+async fn test_http() {
+    let base = serve(Router::new().route("/ok", get(|| async { "ok" }))).await;
+    let indicator = HttpHealthIndicator::new("http".to_owned(), format!("{base}/ok"));
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Up);
+    assert_eq!(detail.details["http_status"], 200);
+    assert_eq!(detail.details["url"], format!("{base}/ok"));
+    assert!(detail.details["latency_ms"].is_u64());
+}
+
+#[tokio::test]
+async fn test_http_status() {
+    let base =
+        serve(Router::new().route("/teapot", get(|| async { StatusCode::IM_A_TEAPOT }))).await;
+    let url = format!("{base}/teapot");
+
+    let detail = HttpHealthIndicator::new("http".to_owned(), url.clone())
+        .details()
+        .await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert_eq!(detail.details["http_status"], 418);
+
+    let detail = HttpHealthIndicator::new("http".to_owned(), url)
+        .with_method(Method::HEAD)
+        .with_up_status(StatusCode::IM_A_TEAPOT)
+        .details()
+        .await;
+    assert_eq!(detail.status, HealthStatus::Up);
+}
+
+#[tokio::test]
+async fn test_http_downstream_health() {
+    let health = Health::builder()
+        .with_indicator(Indicator("db", HealthStatus::Down))
+        .with_indicator(Indicator("cache", HealthStatus::Up))
+        .build();
+    let base = serve(health.router().route("/ok", get(|| async { "ok" }))).await;
+
+    let detail = HttpHealthIndicator::new("downstream".to_owned(), format!("{base}/health"))
+        .with_downstream_health()
+        .details()
+        .await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert_eq!(detail.details["http_status"], 503);
+    assert_eq!(detail.components["db"].status, HealthStatus::Down);
+    assert_eq!(detail.components["cache"].status, HealthStatus::Up);
+
+    let detail = HttpHealthIndicator::new("downstream".to_owned(), format!("{base}/ok"))
+        .with_downstream_health()
+        .details()
+        .await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert!(detail.details["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("invalid health response"));
+}
+
+#[tokio::test]
+async fn test_http_timeout() {
+    let base = serve(Router::new().route(
+        "/slow",
+        get(|| async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            "ok"
+        }),
+    ))
+    .await;
+
+    let detail = HttpHealthIndicator::new("http".to_owned(), format!("{base}/slow"))
+        .with_timeout(Duration::from_millis(100))
+        .details()
+        .await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert!(detail.details.contains_key("error"));
+    assert!(detail.details["latency_ms"].as_u64().unwrap() < 1000);
+}
+
+#[tokio::test]
+async fn test_http_downstream_health_too_large() {
+    let base =
+        serve(Router::new().route("/large", get(|| async { " ".repeat(2 * 1024 * 1024) }))).await;
+
+    let detail = HttpHealthIndicator::new("downstream".to_owned(), format!("{base}/large"))
+        .with_downstream_health()
+        .details()
+        .await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert!(detail.details["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("the health response is larger than"));
+}
+
+#[tokio::test]
+async fn test_http_down() {
+    // Nothing listens on the port once the listener is dropped.
+    let url = {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}/health", listener.local_addr().unwrap())
+    };
+
+    let detail = HttpHealthIndicator::new("http".to_owned(), url)
+        .details()
+        .await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert!(detail.details.contains_key("error"));
+    assert!(!detail.details.contains_key("http_status"));
+}
+
+pub struct Indicator(pub &'static str, pub HealthStatus);
+
+#[async_trait]
+impl HealthIndicator for Indicator {
+    fn name(&self) -> String {
+        self.0.to_owned()
+    }
+
+    async fn details(&self) -> HealthDetail {
+        HealthDetail::new(self.1.clone())
+    }
+}
+
+/// Serves `router` on a local port and returns its base URL.
+pub async fn serve(router: Router) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    base
+}
@@ -3,9 +3,9 @@ mod local {
     use axum::http::StatusCode;
     use axum::routing::get;
     use axum::Router;
-    use axum_health::database::DatabaseHealthIndicator;
+    use axum_health::database::{DatabaseHealthIndicator, ReplicationLagHealthIndicator};
     use axum_health::service::HealthIndicator;
-    use axum_health::{Health, HealthDetail, HealthDetails, HealthStatus};
+    use axum_health::{Health, HealthDetails, HealthStatus};
     use axum_test::TestServer;
     use diesel::r2d2::ConnectionManager;
     use diesel_async::pooled_connection::AsyncDieselConnectionManager;
@@ -101,41 +101,95 @@ mod local {
 
         let response = server.get("/health").await;
         assert_eq!(response.status_code(), StatusCode::OK);
-        let body = response.json::<HealthDetails>();
-
-        let expected = HealthDetails {
-            status: HealthStatus::Up,
-            components: BTreeMap::from_iter([
-                ("diesel-mysql".to_owned(), HealthDetail::up()),
-                ("diesel-bb8".to_owned(), HealthDetail::up()),
-                ("diesel-deadpool".to_owned(), HealthDetail::up()),
-                ("diesel-mobc".to_owned(), HealthDetail::up()),
-                ("sqlx".to_owned(), HealthDetail::up()),
-                ("sea-orm".to_owned(), HealthDetail::up()),
+        let body = statuses(response.json::<HealthDetails>());
+
+        let expected = (
+            HealthStatus::Up,
+            BTreeMap::from_iter([
+                ("diesel-mysql".to_owned(), HealthStatus::Up),
+                ("diesel-bb8".to_owned(), HealthStatus::Up),
+                ("diesel-deadpool".to_owned(), HealthStatus::Up),
+                ("diesel-mobc".to_owned(), HealthStatus::Up),
+                ("sqlx".to_owned(), HealthStatus::Up),
+                ("sea-orm".to_owned(), HealthStatus::Up),
             ]),
-        };
+        );
         assert_eq!(body, expected);
 
         container.stop().await.unwrap();
 
         let response = server.get("/health").await;
         assert_eq!(response.status_code(), StatusCode::SERVICE_UNAVAILABLE);
-        let body = response.json::<HealthDetails>();
-
-        let expected = HealthDetails {
-            status: HealthStatus::Down,
-            components: BTreeMap::from_iter([
-                ("diesel-mysql".to_owned(), HealthDetail::down()),
-                ("diesel-bb8".to_owned(), HealthDetail::down()),
-                ("diesel-deadpool".to_owned(), HealthDetail::down()),
-                ("diesel-mobc".to_owned(), HealthDetail::down()),
-                ("sqlx".to_owned(), HealthDetail::down()),
-                ("sea-orm".to_owned(), HealthDetail::down()),
+        let body = statuses(response.json::<HealthDetails>());
+
+        let expected = (
+            HealthStatus::Down,
+            BTreeMap::from_iter([
+                ("diesel-mysql".to_owned(), HealthStatus::Down),
+                ("diesel-bb8".to_owned(), HealthStatus::Down),
+                ("diesel-deadpool".to_owned(), HealthStatus::Down),
+                ("diesel-mobc".to_owned(), HealthStatus::Down),
+                ("sqlx".to_owned(), HealthStatus::Down),
+                ("sea-orm".to_owned(), HealthStatus::Down),
             ]),
-        };
+        );
         assert_eq!(body, expected);
     }
 
+    #[tokio::test]
+    async fn test_replication_lag() {
+        let container = Mysql::default().start().await.unwrap();
+
+        let url = get_url(&container).await;
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(5))
+            .connect(&url)
+            .await
+            .unwrap();
+
+        // A standalone server is not a replica, so there is no lag to report.
+        let indicator = ReplicationLagHealthIndicator::new("replica".to_owned(), pool)
+            .with_threshold(Duration::from_secs(10), HealthStatus::Down);
+        let detail = indicator.details().await;
+        assert_eq!(detail.status, HealthStatus::Down);
+        assert_eq!(detail.details["error"], "the server is not a replica");
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_lag() {
+        use sqlx::Row;
+
+        let container = Mysql::default().start().await.unwrap();
+
+        let url = get_url(&container).await;
+        let pool = sqlx::mysql::MySqlPool::connect(&url).await.unwrap();
+
+        // `Seconds_Behind_Source` is unsigned like this column, the lag is read unchecked.
+        let row = sqlx::query("SELECT CAST(42 AS UNSIGNED) AS Seconds_Behind_Source")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(row
+            .try_get::<Option<i64>, _>("Seconds_Behind_Source")
+            .is_err());
+        assert_eq!(
+            row.try_get_unchecked::<Option<i64>, _>("Seconds_Behind_Source")
+                .unwrap(),
+            Some(42)
+        );
+    }
+
+    // Details vary with the state of the pools, so only the statuses are compared.
+    fn statuses(details: HealthDetails) -> (HealthStatus, BTreeMap<String, HealthStatus>) {
+        let components = details
+            .components
+            .into_iter()
+            .map(|(name, detail)| (name, detail.status))
+            .collect();
+        (details.status, components)
+    }
+
     async fn get_url(container: &ContainerAsync<Mysql>) -> String {
         format!(
             "mysql://root@{}:{}/test",
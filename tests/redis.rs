@@ -0,0 +1,166 @@
+#![cfg(feature = "_redis")]
+
+use axum_health::database::{
+    DatabaseHealthIndicator, PoolState, RedisHealthIndicator, RedisInfo, TryPingable,
+};
+use axum_health::service::{HealthIndicator, HealthStatus};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+#[cfg(feature = "redis")]
+#[tokio::test]
+async fn test_redis_connection_manager() {
+    let client = redis::Client::open(resp_stub().await).unwrap();
+    let manager = redis::aio::ConnectionManager::new(client).await.unwrap();
+    let indicator = RedisHealthIndicator::from(
+        DatabaseHealthIndicator::new("redis".to_owned(), manager).with_database_info(),
+    )
+    .with_info_field("redis_version".to_owned())
+    .with_info_field("connected_clients".to_owned());
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Up);
+    assert_eq!(detail.details["database"], "Redis");
+    assert_eq!(detail.details["version"], "7.2.4");
+    assert_eq!(detail.details["redis_version"], "7.2.4");
+    assert_eq!(detail.details["connected_clients"], 3);
+}
+
+#[cfg(feature = "redis")]
+#[tokio::test]
+async fn test_redis_multiplexed_connection() {
+    let client = redis::Client::open(resp_stub().await).unwrap();
+    let connection = client.get_multiplexed_async_connection().await.unwrap();
+    let indicator = DatabaseHealthIndicator::new("redis".to_owned(), connection);
+
+    assert_eq!(indicator.details().await.status, HealthStatus::Up);
+}
+
+#[cfg(feature = "deadpool-redis")]
+#[tokio::test]
+async fn test_deadpool_redis() {
+    use deadpool_redis::{Config, Runtime};
+
+    let pool = Config::from_url(resp_stub().await)
+        .create_pool(Some(Runtime::Tokio1))
+        .unwrap();
+    run_pool_test(DatabaseHealthIndicator::new(
+        "deadpool-redis".to_owned(),
+        pool,
+    ))
+    .await;
+}
+
+#[cfg(feature = "deadpool-redis")]
+#[tokio::test]
+async fn test_deadpool_redis_down() {
+    use deadpool_redis::{Config, Runtime};
+
+    // Nothing listens on the port once the listener is dropped.
+    let url = {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("redis://{}", listener.local_addr().unwrap())
+    };
+    let pool = Config::from_url(url)
+        .create_pool(Some(Runtime::Tokio1))
+        .unwrap();
+    let indicator = RedisHealthIndicator::new("deadpool-redis".to_owned(), pool)
+        .with_info_field("redis_version".to_owned());
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert!(detail.details.contains_key("error"));
+    assert!(!detail.details.contains_key("redis_version"));
+}
+
+#[cfg(feature = "bb8-redis")]
+#[tokio::test]
+async fn test_bb8_redis() {
+    use bb8_redis::RedisConnectionManager;
+
+    let manager = RedisConnectionManager::new(resp_stub().await).unwrap();
+    let pool = bb8_redis::bb8::Pool::builder()
+        .build(manager)
+        .await
+        .unwrap();
+    run_pool_test(DatabaseHealthIndicator::new("bb8-redis".to_owned(), pool)).await;
+}
+
+#[cfg(feature = "mobc-redis")]
+#[tokio::test]
+async fn test_mobc_redis() {
+    use mobc_redis::RedisConnectionManager;
+
+    let client = mobc_redis::redis::Client::open(resp_stub().await).unwrap();
+    let pool = mobc_redis::mobc::Pool::builder().build(RedisConnectionManager::new(client));
+    run_pool_test(DatabaseHealthIndicator::new("mobc-redis".to_owned(), pool)).await;
+}
+
+pub async fn run_pool_test<Pool>(database: DatabaseHealthIndicator<Pool>)
+where
+    Pool: TryPingable + PoolState + RedisInfo + Send + Sync + 'static,
+{
+    let indicator = RedisHealthIndicator::from(database.with_pool_statistics())
+        .with_info_field("redis_version".to_owned());
+    let detail = indicator.details().await;
+    assert_eq!(detail.details["redis_version"], "7.2.4");
+    assert_eq!(detail.status, HealthStatus::Up);
+    assert!(detail.details["pool"]["size"].as_u64().unwrap() >= 1);
+}
+
+pub const INFO: &str = "# Server\r\nredis_version:7.2.4\r\nredis_mode:standalone\r\n\r\n\
+    # Clients\r\nconnected_clients:3\r\n";
+
+/// Starts a server speaking just enough RESP to answer `PING` and `INFO`, acknowledging every
+/// other command, and returns its URL.
+pub async fn resp_stub() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("redis://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            tokio::spawn(serve(socket));
+        }
+    });
+    url
+}
+
+pub async fn serve(socket: TcpStream) {
+    let (reader, mut writer) = socket.into_split();
+    let mut reader = BufReader::new(reader);
+    while let Some(command) = read_command(&mut reader).await {
+        let reply = match command.first().map(|name| name.to_ascii_uppercase()) {
+            Some(name) if name == "PING" => "+PONG\r\n".to_owned(),
+            Some(name) if name == "INFO" => format!("${}\r\n{INFO}\r\n", INFO.len()),
+            _ => "+OK\r\n".to_owned(),
+        };
+        if writer.write_all(reply.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Reads a command sent as an array of bulk strings.
+pub async fn read_command<R>(reader: &mut BufReader<R>) -> Option<Vec<String>>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .await
+        .ok()
+        .filter(|read| *read > 0)?;
+    let len = line.trim_end().strip_prefix('*')?.parse::<usize>().ok()?;
+
+    let mut command = Vec::with_capacity(len);
+    for _ in 0..len {
+        line.clear();
+        reader.read_line(&mut line).await.ok()?;
+        let size = line.trim_end().strip_prefix('$')?.parse::<usize>().ok()?;
+        let mut argument = vec![0; size + 2];
+        reader.read_exact(&mut argument).await.ok()?;
+        argument.truncate(size);
+        command.push(String::from_utf8(argument).ok()?);
+    }
+    Some(command)
+}
@@ -1,13 +1,19 @@
 use axum::http::StatusCode;
 use axum::routing::get;
 use axum::Router;
-use axum_health::database::DatabaseHealthIndicator;
+#[cfg(feature = "diesel-r2d2")]
+use axum_health::database::diesel::R2d2Pool;
+#[cfg(any(feature = "sqlx", feature = "sea-orm", feature = "diesel-r2d2"))]
+use axum_health::database::SqliteHealthIndicator;
+use axum_health::database::{
+    DatabaseHealthIndicator, DatabaseMetadata, MigrationHealthIndicator, PendingMigrations,
+    ReplicationLagHealthIndicator, SelectText, TryPingable, ValidationQuery,
+};
 use axum_health::health;
-use axum_health::service::{Health, HealthDetail, HealthDetails, HealthIndicator, HealthStatus};
+use axum_health::service::{Health, HealthDetails, HealthIndicator, HealthStatus};
 use axum_test::TestServer;
 use diesel::r2d2::{ConnectionManager, Pool};
 use sea_orm::DatabaseConnection;
-use std::collections::BTreeMap;
 use std::fs::OpenOptions;
 
 #[cfg(feature = "diesel-r2d2")]
@@ -31,6 +37,54 @@ async fn test_diesel() {
     run_test("diesel-sqlite".to_owned(), indicator).await;
 }
 
+#[cfg(feature = "diesel-r2d2")]
+#[tokio::test]
+async fn test_diesel_exhausted_pool() {
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = path.to_str().unwrap();
+
+    let manager = ConnectionManager::<diesel::SqliteConnection>::new(url);
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_timeout(std::time::Duration::from_secs(1))
+        .build(manager)
+        .unwrap();
+    let _conn = pool.get().unwrap();
+    let indicator = DatabaseHealthIndicator::new("diesel-sqlite".to_owned(), pool.clone());
+
+    // The current thread runtime only makes progress elsewhere if the ping does not block it.
+    let start = std::time::Instant::now();
+    let (detail, woke) = tokio::join!(indicator.details(), async {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        start.elapsed()
+    });
+
+    assert!(woke < std::time::Duration::from_millis(900));
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert!(detail.details.contains_key("error"));
+}
+
+#[cfg(feature = "diesel-r2d2")]
+#[tokio::test]
+async fn test_diesel_overlapping_checks() {
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = path.to_str().unwrap();
+
+    let manager = ConnectionManager::<diesel::SqliteConnection>::new(url);
+    let pool = Pool::builder().max_size(1).build(manager).unwrap();
+    let indicator = DatabaseHealthIndicator::new(
+        "diesel-sqlite".to_owned(),
+        R2d2Pool::new(pool).with_checkout_timeout(std::time::Duration::from_millis(500)),
+    );
+
+    // The second check waits for the first one instead of failing.
+    let (first, second) = tokio::join!(indicator.details(), indicator.details());
+    assert_eq!(first.status, HealthStatus::Up);
+    assert_eq!(second.status, HealthStatus::Up);
+}
+
 #[cfg(feature = "sqlx")]
 #[tokio::test]
 async fn test_sqlx() {
@@ -71,6 +125,417 @@ async fn test_sea_orm() {
     run_test("sea-orm-sqlite".to_owned(), indicator).await;
 }
 
+#[cfg(feature = "sqlx")]
+#[tokio::test]
+async fn test_sqlx_error() {
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("missing").join("test.db");
+    let url = path.to_str().unwrap();
+
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .acquire_timeout(std::time::Duration::from_secs(1))
+        .connect_lazy(url)
+        .unwrap();
+    let indicator = DatabaseHealthIndicator::new("sqlx-sqlite".to_owned(), pool);
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert!(detail.details["error"]
+        .as_str()
+        .unwrap()
+        .contains("unable to open database file"));
+}
+
+#[cfg(feature = "sqlx")]
+#[tokio::test]
+async fn test_sqlx_pool_statistics() {
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = format!("sqlite://{}?mode=rwc", path.to_str().unwrap());
+
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect(&url)
+        .await
+        .unwrap();
+    let _conn = pool.acquire().await.unwrap();
+    let indicator = DatabaseHealthIndicator::new("sqlx-sqlite".to_owned(), pool.clone())
+        .with_usage_threshold(0.5, HealthStatus::Custom("DEGRADED".to_owned()));
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Custom("DEGRADED".to_owned()));
+    assert_eq!(detail.details["pool"]["max_size"], 2);
+    assert!(detail.details["pool"]["in_use"].as_u64().unwrap() >= 1);
+}
+
+#[cfg(feature = "sqlx")]
+#[tokio::test]
+async fn test_sqlx_validation_query() {
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = format!("sqlite://{}?mode=rwc", path.to_str().unwrap());
+
+    let pool = sqlx::sqlite::SqlitePool::connect(&url).await.unwrap();
+    run_validation_test(
+        "sqlx-sqlite".to_owned(),
+        pool.clone(),
+        Some(1),
+        || async move {
+            sqlx::query(CREATE_CRITICAL).execute(&pool).await.unwrap();
+        },
+    )
+    .await;
+}
+
+#[cfg(feature = "diesel-r2d2")]
+#[tokio::test]
+async fn test_diesel_validation_query() {
+    use diesel::connection::SimpleConnection;
+
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = path.to_str().unwrap();
+
+    let manager = ConnectionManager::<diesel::SqliteConnection>::new(url);
+    let pool = Pool::builder().build(manager).unwrap();
+    run_validation_test(
+        "diesel-sqlite".to_owned(),
+        pool.clone(),
+        None,
+        || async move {
+            pool.get().unwrap().batch_execute(CREATE_CRITICAL).unwrap();
+        },
+    )
+    .await;
+}
+
+#[cfg(feature = "sea-orm")]
+#[tokio::test]
+async fn test_sea_orm_validation_query() {
+    use sea_orm::ConnectionTrait;
+
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = format!("sqlite://{}?mode=rwc", path.to_str().unwrap());
+
+    let pool = sqlx::sqlite::SqlitePool::connect(&url).await.unwrap();
+    let database = DatabaseConnection::from(pool);
+    run_validation_test(
+        "sea-orm-sqlite".to_owned(),
+        database.clone(),
+        Some(1),
+        || async move {
+            database.execute_unprepared(CREATE_CRITICAL).await.unwrap();
+        },
+    )
+    .await;
+}
+
+#[cfg(feature = "sqlx")]
+#[tokio::test]
+async fn test_sqlx_database_info() {
+    let pool = sqlx::sqlite::SqlitePool::connect("sqlite::memory:")
+        .await
+        .unwrap();
+    run_database_info_test("sqlx-sqlite".to_owned(), pool).await;
+}
+
+#[cfg(all(feature = "diesel-r2d2", feature = "diesel-sqlite"))]
+#[tokio::test]
+async fn test_diesel_database_info() {
+    let manager = ConnectionManager::<diesel::SqliteConnection>::new(":memory:");
+    let pool = Pool::builder().max_size(1).build(manager).unwrap();
+    run_database_info_test("diesel-sqlite".to_owned(), pool).await;
+}
+
+#[cfg(feature = "sea-orm")]
+#[tokio::test]
+async fn test_sea_orm_database_info() {
+    let pool = sqlx::sqlite::SqlitePool::connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let database = DatabaseConnection::from(pool);
+    run_database_info_test("sea-orm-sqlite".to_owned(), database).await;
+}
+
+#[cfg(feature = "sqlx-migrate")]
+#[tokio::test]
+async fn test_sqlx_migrations() {
+    use axum_health::database::sqlx::SqlxMigrations;
+    use sqlx::migrate::Migrator;
+
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = format!("sqlite://{}?mode=rwc", path.to_str().unwrap());
+    let source = std::path::Path::new("tests/migrations/sqlx");
+
+    let pool = sqlx::sqlite::SqlitePool::connect(&url).await.unwrap();
+    let migrations = SqlxMigrations::new(Migrator::new(source).await.unwrap(), pool.clone());
+    run_migration_test(
+        "sqlx-sqlite".to_owned(),
+        migrations,
+        &path,
+        "1/create users",
+        || async move {
+            Migrator::new(source)
+                .await
+                .unwrap()
+                .run(&pool)
+                .await
+                .unwrap();
+        },
+    )
+    .await;
+}
+
+#[cfg(all(
+    feature = "diesel-migrations",
+    feature = "diesel-r2d2",
+    feature = "diesel-sqlite"
+))]
+#[tokio::test]
+async fn test_diesel_migrations() {
+    use axum_health::database::diesel::DieselMigrations;
+    use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
+
+    const MIGRATIONS: EmbeddedMigrations =
+        diesel_migrations::embed_migrations!("tests/migrations/diesel");
+
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = path.to_str().unwrap();
+
+    let manager = ConnectionManager::<diesel::SqliteConnection>::new(url);
+    let pool = Pool::builder().build(manager).unwrap();
+    run_migration_test(
+        "diesel-sqlite".to_owned(),
+        DieselMigrations::new(MIGRATIONS, pool.clone()),
+        &path,
+        "2025-01-01-000000_create_users",
+        || async move {
+            pool.get()
+                .unwrap()
+                .run_pending_migrations(MIGRATIONS)
+                .unwrap();
+        },
+    )
+    .await;
+}
+
+#[cfg(feature = "sea-orm-migration")]
+#[tokio::test]
+async fn test_sea_orm_migrations() {
+    use axum_health::database::sea_orm::SeaOrmMigrations;
+    use sea_orm_migration::prelude::*;
+
+    struct CreateUsers;
+
+    impl MigrationName for CreateUsers {
+        fn name(&self) -> &str {
+            "m20250101_000000_create_users"
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl MigrationTrait for CreateUsers {
+        async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+            manager
+                .get_connection()
+                .execute_unprepared("CREATE TABLE users (id INTEGER PRIMARY KEY);")
+                .await?;
+            Ok(())
+        }
+    }
+
+    struct Migrator;
+
+    impl MigratorTrait for Migrator {
+        fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+            vec![Box::new(CreateUsers)]
+        }
+    }
+
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = format!("sqlite://{}?mode=rwc", path.to_str().unwrap());
+
+    let pool = sqlx::sqlite::SqlitePool::connect(&url).await.unwrap();
+    let database = DatabaseConnection::from(pool);
+    run_migration_test(
+        "sea-orm-sqlite".to_owned(),
+        SeaOrmMigrations::<Migrator>::new(database.clone()),
+        &path,
+        "m20250101_000000_create_users",
+        || async move {
+            Migrator::up(&database, None).await.unwrap();
+        },
+    )
+    .await;
+}
+
+#[cfg(feature = "sqlx")]
+#[tokio::test]
+async fn test_sqlx_sqlite_indicator() {
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = format!("sqlite://{}?mode=rwc", path.to_str().unwrap());
+
+    let pool = sqlx::sqlite::SqlitePool::connect(&url).await.unwrap();
+    run_sqlite_test("sqlx-sqlite".to_owned(), pool, &path).await;
+}
+
+#[cfg(feature = "diesel-r2d2")]
+#[tokio::test]
+async fn test_diesel_sqlite_indicator() {
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+
+    let manager = ConnectionManager::<diesel::SqliteConnection>::new(path.to_str().unwrap());
+    let pool = Pool::builder().build(manager).unwrap();
+    run_sqlite_test("diesel-sqlite".to_owned(), pool, &path).await;
+}
+
+#[cfg(feature = "sea-orm")]
+#[tokio::test]
+async fn test_sea_orm_sqlite_indicator() {
+    let file = tempfile::tempdir().unwrap();
+    let path = file.path().join("test.db");
+    let url = format!("sqlite://{}?mode=rwc", path.to_str().unwrap());
+
+    let pool = sqlx::sqlite::SqlitePool::connect(&url).await.unwrap();
+    let database = DatabaseConnection::from(pool);
+    run_sqlite_test("sea-orm-sqlite".to_owned(), database, &path).await;
+}
+
+#[cfg(feature = "sqlx")]
+#[tokio::test]
+async fn test_sqlx_sqlite_indicator_in_memory() {
+    let pool = sqlx::sqlite::SqlitePool::connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let indicator = SqliteHealthIndicator::new("sqlx-sqlite".to_owned(), pool).with_quick_check();
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Up);
+    assert_eq!(detail.details["journal_mode"], "memory");
+    assert_eq!(detail.details["quick_check"], "ok");
+    assert!(!detail.details.contains_key("path"));
+}
+
+#[cfg(feature = "sqlx")]
+#[tokio::test]
+async fn test_sqlx_replication_lag() {
+    let pool = sqlx::sqlite::SqlitePool::connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let indicator = ReplicationLagHealthIndicator::new("sqlx-sqlite".to_owned(), pool);
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert_eq!(
+        detail.details["error"],
+        "replication lag is not supported for SQLite"
+    );
+}
+
+#[cfg(any(feature = "sqlx", feature = "sea-orm", feature = "diesel-r2d2"))]
+pub async fn run_sqlite_test<Pool>(name: String, pool: Pool, path: &std::path::Path)
+where
+    Pool: TryPingable + ValidationQuery + SelectText + Send + Sync + 'static,
+{
+    pool.validate("PRAGMA journal_mode = WAL").await.unwrap();
+    pool.validate("CREATE TABLE users (id INTEGER)")
+        .await
+        .unwrap();
+
+    let indicator = SqliteHealthIndicator::new(name, pool)
+        .with_quick_check()
+        .with_read_only_status(HealthStatus::OutOfService);
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Up);
+    assert_eq!(detail.details["journal_mode"], "wal");
+    assert_eq!(detail.details["quick_check"], "ok");
+    assert_eq!(detail.details["path"], path.to_str().unwrap());
+    assert!(detail.details["size_bytes"].as_u64().unwrap() > 0);
+    assert!(detail.details["wal_size_bytes"].is_u64());
+    assert_eq!(detail.details["read_only"], false);
+}
+
+pub async fn run_migration_test<Migrations, F, Fut>(
+    name: String,
+    migrations: Migrations,
+    path: &std::path::Path,
+    pending: &str,
+    migrate: F,
+) where
+    Migrations: PendingMigrations + Send + Sync + 'static,
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    let indicator = MigrationHealthIndicator::new(name, migrations);
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::OutOfService);
+    assert_eq!(detail.details["pending"], serde_json::json!([pending]));
+    // SQLite writes nothing to the file until a table is created.
+    assert_eq!(std::fs::metadata(path).unwrap().len(), 0);
+
+    migrate().await;
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Up);
+    assert!(detail.details.is_empty());
+}
+
+pub async fn run_database_info_test<Pool>(name: String, pool: Pool)
+where
+    Pool: TryPingable + DatabaseMetadata + Send + Sync + 'static,
+{
+    let indicator = DatabaseHealthIndicator::new(name, pool).with_database_info();
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Up);
+    assert_eq!(detail.details["database"], "SQLite");
+    assert!(detail.details["version"].as_str().unwrap().starts_with('3'));
+}
+
+pub const CREATE_CRITICAL: &str =
+    "CREATE TABLE critical (id INTEGER); INSERT INTO critical VALUES (1);";
+
+pub async fn run_validation_test<Pool, F, Fut>(
+    name: String,
+    pool: Pool,
+    rows: Option<u64>,
+    create_table: F,
+) where
+    Pool: TryPingable + ValidationQuery + Send + Sync + 'static,
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    let query = "SELECT 1 FROM critical LIMIT 1";
+    let indicator =
+        DatabaseHealthIndicator::new(name, pool).with_validation_query(query.to_owned());
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Down);
+    assert_eq!(detail.details["validation_query"], query);
+    assert!(detail.details["error"]
+        .as_str()
+        .unwrap()
+        .contains("no such table"));
+
+    create_table().await;
+
+    let detail = indicator.details().await;
+    assert_eq!(detail.status, HealthStatus::Up);
+    assert_eq!(detail.details["validation_query"], query);
+    assert_eq!(
+        detail.details.get("rows").and_then(|rows| rows.as_u64()),
+        rows
+    );
+}
+
 pub async fn run_test(name: String, indicator: impl HealthIndicator + Send + Sync + 'static) {
     let router = Router::new()
         .route("/health", get(health))
@@ -83,10 +548,6 @@ pub async fn run_test(name: String, indicator: impl HealthIndicator + Send + Syn
 
     let body = response.json::<HealthDetails>();
 
-    let expected = HealthDetails {
-        status: HealthStatus::Up,
-        components: BTreeMap::from_iter([(name, HealthDetail::up())]),
-    };
-
-    assert_eq!(body, expected);
+    assert_eq!(body.status, HealthStatus::Up);
+    assert_eq!(body.components[&name].status, HealthStatus::Up);
 }